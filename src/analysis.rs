@@ -0,0 +1,167 @@
+use crate::{GameMode, Playable, Rpssl};
+
+static EPSILON: f64 = 1e-9;
+
+// Mixed strategy equilibrium of a game mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equilibrium {
+    pub hands: Vec<Rpssl>,
+    // Probability of throwing each hand
+    pub probabilities: Vec<f64>,
+    // Expected score of each hand against an opponent playing the
+    // equilibrium, result points and hand points included
+    pub expected: Vec<f64>,
+    // Expected score per match when both play the equilibrium
+    pub value: f64,
+}
+
+impl Equilibrium {
+    pub fn get(&self, hand: Rpssl) -> Option<(f64, f64)> {
+        let i = self.hands.iter().position(|h| *h == hand)?;
+        Some((self.probabilities[i], self.expected[i]))
+    }
+}
+
+// Score for throwing hands[i] against hands[j], as counted in the standings
+pub fn payoff_matrix(game_mode: GameMode) -> Vec<Vec<f64>> {
+    let hands = game_mode.hands();
+    hands.iter().map(|h1| {
+        hands.iter().map(|h2| {
+            (h1.result(h2).get_score(game_mode) + h1.get_score()) as f64
+        }).collect()
+    }).collect()
+}
+
+// Maximises c.y subject to a.y <= b and y >= 0, with b >= 0.
+// Returns the optimal y, the dual solution and the optimal value
+fn simplex(a: &[Vec<f64>], b: &[f64], c: &[f64]) -> (Vec<f64>, Vec<f64>, f64) {
+    let (m, n) = (a.len(), c.len());
+    let width = n + m + 1;
+    let mut tableau = a.iter().zip(b).enumerate().map(|(i, (row, bi))| {
+        let mut t = row.clone();
+        t.extend((0..m).map(|k| if k == i { 1.0 } else { 0.0 }));
+        t.push(*bi);
+        t
+    }).collect::<Vec<_>>();
+    let mut objective = c.iter().map(|ci| -ci).collect::<Vec<_>>();
+    objective.extend(vec![0.0; m + 1]);
+    let mut basis = (n..n + m).collect::<Vec<_>>();
+
+    // Bland's rule, so that the iteration always terminates
+    while let Some(col) = (0..width - 1).find(|&j| objective[j] < -EPSILON) {
+        let row = (0..m)
+            .filter(|&i| tableau[i][col] > EPSILON)
+            .min_by(|&i, &k| {
+                let (ri, rk) = (tableau[i][width - 1] / tableau[i][col], tableau[k][width - 1] / tableau[k][col]);
+                ri.total_cmp(&rk).then(basis[i].cmp(&basis[k]))
+            })
+            .expect("Problem should be bounded");
+        let pivot = tableau[row][col];
+        for v in tableau[row].iter_mut() {
+            *v /= pivot;
+        }
+        let pivot_row = tableau[row].clone();
+        for (i, r) in tableau.iter_mut().enumerate() {
+            if i != row {
+                let factor = r[col];
+                for (v, p) in r.iter_mut().zip(&pivot_row) {
+                    *v -= factor * p;
+                }
+            }
+        }
+        let factor = objective[col];
+        for (v, p) in objective.iter_mut().zip(&pivot_row) {
+            *v -= factor * p;
+        }
+        basis[row] = col;
+    }
+
+    let mut y = vec![0.0; n];
+    for (i, &var) in basis.iter().enumerate() {
+        if var < n {
+            y[var] = tableau[i][width - 1];
+        }
+    }
+    let dual = objective[n..n + m].to_vec();
+    (y, dual, objective[width - 1])
+}
+
+// Optimal mixed strategy of the row player in a zero-sum game, and the
+// value of the game
+pub fn solve_zero_sum(matrix: &[Vec<f64>]) -> (Vec<f64>, f64) {
+    // Shift all payoffs positive, then the row player's strategy is the
+    // dual of max sum(y) s.t. matrix.y <= 1
+    let min = matrix.iter().flatten().copied().fold(f64::INFINITY, f64::min);
+    let shift = 1.0 - min;
+    let shifted = matrix.iter()
+        .map(|row| row.iter().map(|v| v + shift).collect())
+        .collect::<Vec<Vec<f64>>>();
+    let n = shifted[0].len();
+    let (_y, dual, total) = simplex(&shifted, &vec![1.0; matrix.len()], &vec![1.0; n]);
+    let strategy = dual.iter().map(|x| x / total).collect();
+    (strategy, 1.0 / total - shift)
+}
+
+// Each player wants the highest score of their own. The opponent's hand
+// points don't depend on our throw, so subtracting them doesn't change
+// anyone's best response. What is left is result points + own hand points -
+// opponent's hand points, a zero-sum game that can be solved exactly
+pub fn solve(game_mode: GameMode) -> Equilibrium {
+    let hands = game_mode.hands();
+    let payoff = payoff_matrix(game_mode);
+    let zero_sum = payoff.iter().map(|row| {
+        row.iter().zip(&hands).map(|(v, h2)| v - h2.get_score() as f64).collect()
+    }).collect::<Vec<Vec<f64>>>();
+    let (probabilities, _value) = solve_zero_sum(&zero_sum);
+    let expected = payoff.iter().map(|row| {
+        row.iter().zip(&probabilities).map(|(v, p)| v * p).sum::<f64>()
+    }).collect::<Vec<f64>>();
+    let value = expected.iter().zip(&probabilities).map(|(e, p)| e * p).sum();
+    Equilibrium {hands, probabilities, expected, value}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_equilibrium(game_mode: GameMode) -> Equilibrium {
+        let eq = solve(game_mode);
+        assert!((eq.probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        assert!(eq.probabilities.iter().all(|p| *p >= -1e-9));
+        // No hand does better than the equilibrium, and all hands that
+        // are played do equally well
+        let best = eq.expected.iter().copied().fold(f64::MIN, f64::max);
+        for (p, e) in eq.probabilities.iter().zip(&eq.expected) {
+            if *p > 1e-6 {
+                assert!((best - e).abs() < 1e-6);
+            }
+        }
+        assert!((eq.value - best).abs() < 1e-6);
+        eq
+    }
+
+    #[test]
+    fn zero_sum() {
+        // Classic rock paper scissors is solved by uniform play
+        let rps = vec![
+            vec![0.0, -1.0, 1.0],
+            vec![1.0, 0.0, -1.0],
+            vec![-1.0, 1.0, 0.0],
+        ];
+        let (strategy, value) = solve_zero_sum(&rps);
+        for p in strategy {
+            assert!((p - 1.0 / 3.0).abs() < 1e-9);
+        }
+        assert!(value.abs() < 1e-9);
+    }
+
+    #[test]
+    fn equilibria() {
+        let rps = check_equilibrium(GameMode::RPS);
+        assert_eq!(rps.hands.len(), 3);
+        let rpssl = check_equilibrium(GameMode::RPSSL);
+        assert_eq!(rpssl.hands.len(), 5);
+        // Uniform play isn't optimal with hand points
+        assert!(rpssl.probabilities.iter().any(|p| (p - 0.2).abs() > 1e-3));
+    }
+}
@@ -0,0 +1,125 @@
+use std::env;
+use std::net::SocketAddr;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use axum::{Json, Router};
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use pepulo_rps::error::GameError;
+use pepulo_rps::commit::random_nonce;
+use pepulo_rps::remote::{Commit, OpenMatch, RemoteMatch, RemoteStatus, Reveal, ThrowBox, API_COMMIT, API_MATCH, API_REVEAL, API_START, API_STATUS, OPERATOR_HEADER};
+
+static USAGE: &str = "Usage: rps-server [options]
+
+Keeps the throws players send from their own devices until the
+operator's tab records the result. Run it next to `trunk serve`,
+which forwards the /api/ addresses here.
+
+Only the operator's tab may open matches and start countdowns. It
+needs the operator secret, which is printed at start unless given.
+
+Options:
+    -a, --address ADDR    Address to listen on (default 127.0.0.1:3000)
+    -s, --secret SECRET   Operator secret (default random)
+    -h, --help            Print this help";
+
+type Throws = Arc<Mutex<ThrowBox>>;
+
+// Countdowns and late hands are timed by the server clock only
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64)
+}
+
+fn parse_args(args: &[String]) -> Result<(SocketAddr, Option<String>), String> {
+    let mut address = SocketAddr::from(([127, 0, 0, 1], 3000));
+    let mut secret = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "-a" | "--address" => address = value()?.parse().map_err(|e| format!("{}", e))?,
+            "-s" | "--secret" => secret = Some(value()?.clone()).filter(|s| !s.is_empty()),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            },
+            other => return Err(format!("Unknown argument {}", other)),
+        }
+    }
+    Ok((address, secret))
+}
+
+async fn get_match(State(throws): State<Throws>) -> Json<Option<RemoteMatch>> {
+    Json(throws.lock().expect("Lock should not be poisoned").get_open().cloned())
+}
+
+fn respond(result: Result<(), GameError>) -> Result<StatusCode, (StatusCode, Json<GameError>)> {
+    result.map(|_| StatusCode::NO_CONTENT).map_err(|e| match e {
+        GameError::BadToken | GameError::NotOperator => (StatusCode::FORBIDDEN, Json(e)),
+        e => (StatusCode::CONFLICT, Json(e)),
+    })
+}
+
+fn operator_secret(headers: &HeaderMap) -> &str {
+    headers.get(OPERATOR_HEADER).and_then(|v| v.to_str().ok()).unwrap_or("")
+}
+
+async fn open_match(State(throws): State<Throws>, headers: HeaderMap, Json(open): Json<OpenMatch>) -> Result<StatusCode, (StatusCode, Json<GameError>)> {
+    respond(throws.lock().expect("Lock should not be poisoned").open(operator_secret(&headers), open))
+}
+
+async fn close_match(State(throws): State<Throws>, headers: HeaderMap, Json(game_id): Json<(u16, u16, u16)>) -> Result<StatusCode, (StatusCode, Json<GameError>)> {
+    respond(throws.lock().expect("Lock should not be poisoned").close(operator_secret(&headers), game_id))
+}
+
+async fn commit(State(throws): State<Throws>, Json(commit): Json<Commit>) -> Result<StatusCode, (StatusCode, Json<GameError>)> {
+    respond(throws.lock().expect("Lock should not be poisoned").commit(commit, now()))
+}
+
+// Hashes are checked here, and once more when the result is added
+async fn reveal(State(throws): State<Throws>, Json(reveal): Json<Reveal>) -> Result<StatusCode, (StatusCode, Json<GameError>)> {
+    respond(throws.lock().expect("Lock should not be poisoned").reveal(reveal, now()))
+}
+
+async fn start(State(throws): State<Throws>, headers: HeaderMap, Json(game_id): Json<(u16, u16, u16)>) -> Result<StatusCode, (StatusCode, Json<GameError>)> {
+    respond(throws.lock().expect("Lock should not be poisoned").start(operator_secret(&headers), game_id, now()))
+}
+
+async fn get_status(State(throws): State<Throws>) -> Json<Option<RemoteStatus>> {
+    Json(throws.lock().expect("Lock should not be poisoned").status(now()))
+}
+
+#[tokio::main]
+async fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let (address, secret) = match parse_args(&args) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+    let secret = secret.unwrap_or_else(|| random_nonce(&mut rand::thread_rng()));
+    let app = Router::new()
+        .route(API_MATCH, get(get_match).put(open_match).delete(close_match))
+        .route(API_COMMIT, post(commit))
+        .route(API_REVEAL, post(reveal))
+        .route(API_START, post(start))
+        .route(API_STATUS, get(get_status))
+        .with_state(Arc::new(Mutex::new(ThrowBox::new(&secret))));
+    let listener = match tokio::net::TcpListener::bind(address).await {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("Could not listen on {}: {}", address, e);
+            process::exit(1);
+        }
+    };
+    println!("Listening on {}", address);
+    println!("Operator secret: {}", secret);
+    if let Err(e) = axum::serve(listener, app).await {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
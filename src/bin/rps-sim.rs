@@ -0,0 +1,133 @@
+use std::env;
+use std::process;
+use strum::IntoEnumIterator;
+use pepulo_rps::GameMode;
+use pepulo_rps::sim::{run, SimConfig, SimReport};
+use pepulo_rps::strategy::StrategyKind;
+
+static USAGE: &str = "Usage: rps-sim [options]
+
+Plays round robin tournaments between strategy bots.
+
+Options:
+    -n, --tournaments N   Number of tournaments to play (default 1000)
+    -r, --rounds N        Rounds in each tournament (default 1)
+    -m, --mode MODE       rps or rpssl (default rpssl)
+    -s, --seed N          Seed of the first tournament (default 0)
+    -b, --bots LIST       Comma separated strategies (default all)
+        --json            Print the report as JSON
+    -h, --help            Print this help
+
+Strategies: ";
+
+fn usage() -> String {
+    let names = StrategyKind::iter().map(strategy_arg).collect::<Vec<_>>();
+    format!("{}{}", USAGE, names.join(", "))
+}
+
+fn strategy_arg(kind: StrategyKind) -> String {
+    kind.str().to_lowercase().replace(' ', "-")
+}
+
+fn parse_args(args: &[String]) -> Result<(SimConfig, bool), String> {
+    let mut config = SimConfig {
+        tournaments: 1000,
+        rounds: 1,
+        game_mode: GameMode::RPSSL,
+        seed: 0,
+        bots: StrategyKind::iter().collect(),
+    };
+    let mut json = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "-n" | "--tournaments" => config.tournaments = value()?.parse().map_err(|e| format!("{}", e))?,
+            "-r" | "--rounds" => config.rounds = value()?.parse().map_err(|e| format!("{}", e))?,
+            "-s" | "--seed" => config.seed = value()?.parse().map_err(|e| format!("{}", e))?,
+            "-m" | "--mode" => config.game_mode = match value()?.to_lowercase().as_str() {
+                "rps" => GameMode::RPS,
+                "rpssl" => GameMode::RPSSL,
+                other => return Err(format!("Unknown mode {}", other)),
+            },
+            "-b" | "--bots" => {
+                config.bots = value()?.split(',').map(|name| {
+                    StrategyKind::iter()
+                        .find(|k| strategy_arg(*k) == name.trim().to_lowercase())
+                        .ok_or(format!("Unknown strategy {}", name))
+                }).collect::<Result<Vec<_>, _>>()?;
+            },
+            "--json" => json = true,
+            "-h" | "--help" => {
+                println!("{}", usage());
+                process::exit(0);
+            },
+            other => return Err(format!("Unknown argument {}", other)),
+        }
+    }
+    if config.rounds == 0 {
+        return Err("Rounds must be at least 1".to_string());
+    }
+    Ok((config, json))
+}
+
+fn print_report(report: &SimReport) {
+    println!("{} tournaments, {} round(s), mode {}, seed {}",
+        report.tournaments, report.rounds, report.game_mode.str(), report.seed);
+    println!();
+    println!("Standings");
+    print!("{:<16}{:>10}", "Bot", "Mean");
+    for place in 1..=report.bots.len() {
+        print!("{:>8}", format!("#{}", place));
+    }
+    println!("{:>8}{:>8}{:>8}", "W", "D", "L");
+    let mut bots = report.bots.iter().collect::<Vec<_>>();
+    bots.sort_by(|a, b| b.mean_score.total_cmp(&a.mean_score));
+    for bot in bots {
+        print!("{:<16}{:>10.2}", bot.name, bot.mean_score);
+        for n in &bot.positions {
+            print!("{:>7.1}%", 100.0 * *n as f64 / report.tournaments.max(1) as f64);
+        }
+        println!("{:>8}{:>8}{:>8}", bot.wins, bot.draws, bot.losses);
+    }
+    println!();
+    println!("Head to head win rates (row vs column)");
+    print!("{:<16}", "");
+    for bot in &report.bots {
+        print!("{:>16}", bot.name);
+    }
+    println!();
+    for bot in &report.bots {
+        print!("{:<16}", bot.name);
+        for opponent in &report.bots {
+            match report.head_to_head.iter().find(|p| (p.bot == bot.name) & (p.opponent == opponent.name)) {
+                Some(pair) => print!("{:>15.1}%", 100.0 * pair.win_rate()),
+                None => print!("{:>16}", "-"),
+            }
+        }
+        println!();
+    }
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let (config, json) = match parse_args(&args) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, usage());
+            process::exit(2);
+        }
+    };
+    let report = match run(&config) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report).expect("Report should serialize"));
+    } else {
+        print_report(&report);
+    }
+}
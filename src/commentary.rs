@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+use crate::{Game, Match, Playable, RpsResult};
+use crate::i18n::{lookup, tr, tr_args, Language};
+use crate::standings::{StandingsHistory, Surprise};
+
+impl Game {
+    // One sentence per played match, e.g. "Bob's 🧛 vaporizes Alice's 🪨 —
+    // Bob takes the lead!". The standings part compares the standings just
+    // before and after the match
+    pub fn get_commentary(&self, language: Language) -> BTreeMap<(u16, u16, u16), String> {
+        let history = self.get_standings_history();
+        history.matches.iter().enumerate()
+            .filter_map(|(step, game_id)| {
+                let m = self.match_list.get(game_id)?;
+                Some((*game_id, self.comment(m, &history, step, language)?))
+            })
+            .collect()
+    }
+
+    // Commentary of the latest result, for the result banner
+    pub fn get_latest_commentary(&self, language: Language) -> Option<String> {
+        let (m, _) = self.get_played_games().last().copied()?;
+        self.get_commentary(language).remove(&(m.player1, m.player2, m.round))
+    }
+
+    fn comment(&self, m: &Match, history: &StandingsHistory, step: usize, language: Language) -> Option<String> {
+        let name = |pid: u16| self.player_list.get(&pid).map_or("", |p| p.display_name()).to_string();
+        let (winner, loser, winning_hand, losing_hand) = match m.result.clone()? {
+            RpsResult::Win => (m.player1, m.player2, m.play1, m.play2),
+            RpsResult::Lose => (m.player2, m.player1, m.play2, m.play1),
+            RpsResult::Draw => return Some(tr_args(language, "commentary.draw", &[&name(m.player1), &name(m.player2), m.play1.str()])),
+        };
+        let beat = self.game_mode.rules().beat(winning_hand, losing_hand)?.clone();
+        let verb = lookup(language, &beat.verb_key()).unwrap_or(beat.verb);
+        let sentence = tr_args(language, "commentary.win", &[&name(winner), winning_hand.str(), verb, &name(loser), losing_hand.str()]);
+
+        // Ranks before the match are at step, after it at step + 1
+        let rank = |pid: u16, at: usize| history.ranks.get(&pid).and_then(|r| r.get(at)).copied();
+        let leaders = |at: usize| history.ranks.values().filter(|r| r.get(at) == Some(&1)).count();
+        let context = if (rank(winner, step + 1) == Some(1)) & (leaders(step + 1) == 1) & ((rank(winner, step) != Some(1)) | (leaders(step) > 1)) {
+            Some(tr_args(language, "commentary.lead", &[&name(winner)]))
+        } else {
+            history.surprise(winner, loser, step).map(|surprise| match surprise {
+                Surprise::LeaderBeaten => tr_args(language, "commentary.leader_beaten", &[&name(loser)]),
+                Surprise::Upset => tr(language, "commentary.upset").to_string(),
+            })
+        };
+        Some(match context {
+            Some(context) => format!("{} — {}", sentence, context),
+            None => sentence,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameMode, Rpssl};
+
+    #[test]
+    fn commentary() {
+        let mut game = Game::with_seed(1);
+        let _ = game.set_mode(GameMode::RPSSL);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_player("Charlie");
+        assert_eq!(game.get_latest_commentary(Language::En), None);
+
+        game.add_result((1, 2, 1), Rpssl::Rock, Rpssl::Vampire);
+        assert_eq!(game.get_latest_commentary(Language::En).unwrap(), "Bob's 🧛 vaporizes Alice's 🪨 — Bob takes the lead!");
+        game.add_result((2, 3, 1), Rpssl::Vampire, Rpssl::Lizard);
+        assert_eq!(game.get_latest_commentary(Language::En).unwrap(), "Charlie's 🦎 poisons Bob's 🧛 — The leader Bob falls!");
+        game.add_result((1, 3, 1), Rpssl::Paper, Rpssl::Paper);
+        assert_eq!(game.get_latest_commentary(Language::En).unwrap(), "Alice and Charlie both throw 📜, a draw");
+        assert!(game.get_latest_commentary(Language::Fi).unwrap().contains("tasapeli"));
+
+        let commentary = game.get_commentary(Language::Fi);
+        assert_eq!(commentary.len(), 3);
+        assert!(commentary[&(1, 2, 1)].starts_with("🧛 höyrystää 🪨"));
+    }
+}
@@ -0,0 +1,107 @@
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use crate::{Action, Game, Rpssl};
+use crate::error::GameError;
+
+// Hand locked in as a hash before either player saw the other's hand,
+// then revealed with its nonce. Anyone can check it afterwards
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Commitment {
+    pub hash: String,
+    pub hand: Rpssl,
+    pub nonce: String,
+}
+
+impl Commitment {
+    pub fn new(hand: Rpssl, nonce: &str) -> Commitment {
+        Commitment {hash: hash(hand, nonce), hand, nonce: nonce.to_string()}
+    }
+
+    pub fn verify(&self) -> bool {
+        self.hash == hash(self.hand, &self.nonce)
+    }
+}
+
+// Hex encoded SHA-256 of the hand's shortcut and the nonce
+pub fn hash(hand: Rpssl, nonce: &str) -> String {
+    let digest = Sha256::digest(format!("{}:{}", hand.shortcut(), nonce));
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Without the nonce the few possible hands could be guessed from the hash
+pub fn random_nonce<R: Rng>(rng: &mut R) -> String {
+    (0..16).map(|_| format!("{:02x}", rng.gen::<u8>())).collect()
+}
+
+impl Game {
+    // Adds a result from revealed commitments, which are kept with the
+    // match. Sides without a commitment must be bots or late
+    pub fn add_committed_result(
+        &mut self,
+        game_id: (u16, u16, u16),
+        commitment1: Option<Commitment>,
+        commitment2: Option<Commitment>,
+    ) -> Result<(), GameError> {
+        for commitment in [&commitment1, &commitment2].into_iter().flatten() {
+            if !commitment.verify() {
+                return Err(GameError::BadCommitment);
+            }
+            if !self.game_mode.hands().contains(&commitment.hand) {
+                return Err(GameError::InvalidHand);
+            }
+        }
+        let hand = |c: &Option<Commitment>| c.as_ref().map(|c| c.hand);
+        let (throw1, throw2) = self.timed_throws(game_id, hand(&commitment1), hand(&commitment2))?;
+        self.set_result(game_id, throw1, throw2);
+        if let Some(m) = self.match_list.get_mut(&game_id) {
+            m.commitments = (commitment1.clone(), commitment2.clone());
+        }
+        self.actions.push(Action::AddCommittedResult(game_id, commitment1, commitment2));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use crate::strategy::StrategyKind;
+
+    #[test]
+    fn commit_reveal() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let nonce = random_nonce(&mut rng);
+        assert_eq!(nonce.len(), 32);
+        assert_ne!(nonce, random_nonce(&mut rng));
+        let rock = Commitment::new(Rpssl::Rock, &nonce);
+        assert!(rock.verify());
+        assert_eq!(rock.hash.len(), 64);
+        // Changing the hand after committing is caught
+        let changed = Commitment {hand: Rpssl::Paper, ..rock.clone()};
+        assert!(!changed.verify());
+
+        let mut game = Game::with_seed(1);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_bot("Robo", StrategyKind::Random);
+        let paper = Commitment::new(Rpssl::Paper, "b");
+        assert_eq!(game.add_committed_result((1, 2, 1), Some(changed), Some(paper.clone())), Err(GameError::BadCommitment));
+        assert_eq!(game.add_committed_result((1, 2, 1), Some(rock.clone()), None), Err(GameError::MissingHands));
+        assert!(game.add_committed_result((1, 2, 1), Some(rock.clone()), Some(paper.clone())).is_ok());
+        let m = game.get_match((1, 2, 1)).unwrap();
+        assert_eq!((m.play1, m.play2), (Rpssl::Rock, Rpssl::Paper));
+        assert_eq!(m.commitments, (Some(rock.clone()), Some(paper)));
+        assert!(game.add_committed_result((1, 3, 1), Some(rock), None).is_ok());
+        assert_ne!(game.get_match((1, 3, 1)).unwrap().play2, Rpssl::None);
+
+        let replayed = Game::from_saved(&game.save()).unwrap();
+        assert_eq!(replayed.get_match((1, 2, 1)), game.get_match((1, 2, 1)));
+        assert_eq!(replayed.get_match((1, 3, 1)), game.get_match((1, 3, 1)));
+
+        // Corrected hands drop the commitments
+        assert!(game.edit_result((1, 2, 1), Rpssl::Scissors, Rpssl::Paper).is_ok());
+        assert_eq!(game.get_match((1, 2, 1)).unwrap().commitments, (None, None));
+    }
+}
@@ -0,0 +1,143 @@
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use crate::{Action, Game, Playable, RpsResult, Rpssl};
+use crate::error::GameError;
+
+// Number of words in "Kivi! Paperi! Sakset!"
+pub static BEATS: u64 = 3;
+
+// What happens to a player whose hand is not in when the countdown ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LatePenalty {
+    // The late player throws a hand that loses to the opponent's hand
+    Loss,
+    // The late player's hand is drawn at random
+    RandomThrow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Countdown {
+    // Time between the words of the countdown
+    pub beat_ms: u64,
+    pub penalty: LatePenalty,
+}
+
+impl Default for Countdown {
+    fn default() -> Self {
+        Countdown {beat_ms: 700, penalty: LatePenalty::RandomThrow}
+    }
+}
+
+impl Countdown {
+    pub fn duration_ms(&self) -> u64 {
+        BEATS * self.beat_ms
+    }
+
+    // Word shown after elapsed time, None once hands are locked
+    pub fn beat_at(&self, elapsed_ms: u64) -> Option<usize> {
+        let beat = elapsed_ms / self.beat_ms.max(1);
+        (beat < BEATS).then_some(beat as usize)
+    }
+}
+
+impl Game {
+    pub fn set_countdown(&mut self, countdown: Option<Countdown>) {
+        self.countdown = countdown;
+        self.actions.push(Action::SetCountdown(countdown));
+    }
+
+    pub fn get_countdown(&self) -> Option<Countdown> {
+        self.countdown
+    }
+
+    // Adds the result of a timed match. A None hand came too late and is
+    // replaced according to the late penalty
+    pub fn add_timed_result(&mut self, game_id: (u16, u16, u16), play1: Option<Rpssl>, play2: Option<Rpssl>) -> Result<(), GameError> {
+        let (throw1, throw2) = self.timed_throws(game_id, play1, play2)?;
+        self.set_result(game_id, throw1, throw2);
+        self.actions.push(Action::AddTimedResult(game_id, play1, play2));
+        Ok(())
+    }
+
+    // Throws of a match where None is a late hand. Bots are never late.
+    // Everything is checked before random draws, so that a refused call
+    // doesn't change the outcome of the replay
+    pub(crate) fn timed_throws(&mut self, game_id: (u16, u16, u16), play1: Option<Rpssl>, play2: Option<Rpssl>) -> Result<(Rpssl, Rpssl), GameError> {
+        let m = self.match_list.get(&game_id).ok_or(GameError::NoSuchGame)?;
+        if m.result.is_some() {
+            return Err(GameError::AlreadyPlayed);
+        }
+        let is_bot = |pid: u16| self.player_list.get(&pid).is_some_and(|p| p.is_bot());
+        let late1 = play1.is_none() & !is_bot(m.player1);
+        let late2 = play2.is_none() & !is_bot(m.player2);
+        let penalty = match self.countdown {
+            Some(c) => c.penalty,
+            None if late1 | late2 => return Err(GameError::MissingHands),
+            None => LatePenalty::RandomThrow,
+        };
+        let (throw1, throw2) = self.bot_throws(game_id, play1.unwrap_or(Rpssl::None), play2.unwrap_or(Rpssl::None));
+        Ok(match (late1, late2, penalty) {
+            (false, false, _) => (throw1, throw2),
+            (true, false, LatePenalty::Loss) => (self.losing_hand(throw2), throw2),
+            (false, true, LatePenalty::Loss) => (throw1, self.losing_hand(throw1)),
+            // Nobody to lose to when both are late
+            (late1, late2, _) => (
+                if late1 { self.random_hand() } else { throw1 },
+                if late2 { self.random_hand() } else { throw2 },
+            ),
+        })
+    }
+
+    // Cheapest hand that loses to the given one
+    fn losing_hand(&self, winner: Rpssl) -> Rpssl {
+        self.game_mode.hands().into_iter()
+            .filter(|h| h.result(&winner) == RpsResult::Lose)
+            .min_by_key(|h| h.get_score())
+            .unwrap_or(Rpssl::None)
+    }
+
+    fn random_hand(&mut self) -> Rpssl {
+        let hands = self.game_mode.hands();
+        hands[self.rng.gen_range(0..hands.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameMode;
+
+    #[test]
+    fn countdown() {
+        let countdown = Countdown {beat_ms: 500, penalty: LatePenalty::Loss};
+        assert_eq!(countdown.duration_ms(), 1500);
+        assert_eq!(countdown.beat_at(0), Some(0));
+        assert_eq!(countdown.beat_at(999), Some(1));
+        assert_eq!(countdown.beat_at(1500), None);
+
+        let mut game = Game::with_seed(1);
+        let _ = game.set_mode(GameMode::RPSSL);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_player("Charlie");
+        // Late hands need a penalty rule
+        assert_eq!(game.add_timed_result((1, 2, 1), Some(Rpssl::Rock), None), Err(GameError::MissingHands));
+        game.set_countdown(Some(countdown));
+
+        assert!(game.add_timed_result((1, 2, 1), Some(Rpssl::Rock), None).is_ok());
+        let m = game.get_match((1, 2, 1)).unwrap();
+        assert_eq!(m.result, Some(RpsResult::Win));
+        // Scissors and lizard lose to rock, scissors give fewer hand points
+        assert_eq!(m.play2, Rpssl::Scissors);
+
+        game.set_countdown(Some(Countdown {penalty: LatePenalty::RandomThrow, ..countdown}));
+        assert!(game.add_timed_result((1, 3, 1), None, None).is_ok());
+        let m = game.get_match((1, 3, 1)).unwrap();
+        assert!(m.play1.is_some() & m.play2.is_some());
+        assert!(game.add_timed_result((1, 3, 1), None, None).is_err());
+
+        let replayed = Game::from_saved(&game.save()).unwrap();
+        assert_eq!(replayed.get_match((1, 3, 1)), game.get_match((1, 3, 1)));
+        assert_eq!(replayed.get_countdown(), game.get_countdown());
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+use serde::{Serialize, Deserialize};
+use crate::i18n::{tr, tr_args, tr_n, Language};
+
+// Errors returned by the library. Shown to users with localized()
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameError {
+    PlayerExists(String),
+    MatchExists,
+    NoSuchGame,
+    NoSuchPlayer,
+    NoSuchTournament,
+    NotPlayed,
+    AlreadyPlayed,
+    MissingHands,
+    // Game mode can't be changed after results have been added
+    ResultsExist,
+    // Number of played matches with hands the new game mode doesn't have
+    IncompatibleResults(usize),
+    MinRounds,
+    // Number of played matches in the round that would be removed
+    RoundPlayed(usize),
+    TooFewBots,
+    // Hand is not part of the game mode
+    InvalidHand,
+    AlreadyThrown,
+    // Hand can only be revealed after both players have committed
+    NotCommitted,
+    // Revealed hand does not match the committed hash
+    BadCommitment,
+    // Hand came after the countdown ended
+    TooLate,
+    // Throw did not come with the player's own link
+    BadToken,
+    // Only the operator can open, close and start matches
+    NotOperator,
+    // Profile colour is not #rrggbb
+    InvalidColour,
+    // Avatar is not an image data URL or is too large
+    InvalidAvatar,
+    // Bulk entry line is not "code hand hand"
+    UnreadableLine,
+    EmptyQuote,
+    NoSuchQuote,
+}
+
+impl GameError {
+    pub fn localized(&self, language: Language) -> String {
+        match self {
+            Self::PlayerExists(name) => tr_args(language, "error.player_exists", &[name]),
+            Self::MatchExists => tr(language, "error.match_exists").to_string(),
+            Self::NoSuchGame => tr(language, "error.no_such_game").to_string(),
+            Self::NoSuchPlayer => tr(language, "error.no_such_player").to_string(),
+            Self::NoSuchTournament => tr(language, "error.no_such_tournament").to_string(),
+            Self::NotPlayed => tr(language, "error.not_played").to_string(),
+            Self::AlreadyPlayed => tr(language, "error.already_played").to_string(),
+            Self::MissingHands => tr(language, "error.missing_hands").to_string(),
+            Self::ResultsExist => tr(language, "error.results_exist").to_string(),
+            Self::IncompatibleResults(n) => tr_n(language, "error.incompatible_results", *n as i64),
+            Self::MinRounds => tr(language, "error.min_rounds").to_string(),
+            Self::RoundPlayed(n) => tr_n(language, "error.round_played", *n as i64),
+            Self::TooFewBots => tr(language, "error.too_few_bots").to_string(),
+            Self::InvalidHand => tr(language, "error.invalid_hand").to_string(),
+            Self::AlreadyThrown => tr(language, "error.already_thrown").to_string(),
+            Self::NotCommitted => tr(language, "error.not_committed").to_string(),
+            Self::BadCommitment => tr(language, "error.bad_commitment").to_string(),
+            Self::TooLate => tr(language, "error.too_late").to_string(),
+            Self::BadToken => tr(language, "error.bad_token").to_string(),
+            Self::NotOperator => tr(language, "error.not_operator").to_string(),
+            Self::InvalidColour => tr(language, "error.invalid_colour").to_string(),
+            Self::InvalidAvatar => tr(language, "error.invalid_avatar").to_string(),
+            Self::UnreadableLine => tr(language, "error.unreadable_line").to_string(),
+            Self::EmptyQuote => tr(language, "error.empty_quote").to_string(),
+            Self::NoSuchQuote => tr(language, "error.no_such_quote").to_string(),
+        }
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.localized(Language::En))
+    }
+}
+
+impl std::error::Error for GameError {}
@@ -0,0 +1,361 @@
+use serde::{Serialize, Deserialize};
+use strum_macros::EnumIter;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, EnumIter, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    Fi,
+    En,
+    Sv,
+}
+
+impl Language {
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Fi => "fi",
+            Self::En => "en",
+            Self::Sv => "sv",
+        }
+    }
+
+    // Name of the language in the language itself
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Fi => "Suomi",
+            Self::En => "English",
+            Self::Sv => "Svenska",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Language> {
+        match code {
+            "fi" => Some(Self::Fi),
+            "en" => Some(Self::En),
+            "sv" => Some(Self::Sv),
+            _ => None,
+        }
+    }
+
+    // Plural form suffix for n. All supported languages only separate
+    // one from the rest
+    fn plural(&self, n: i64) -> &str {
+        if n == 1 { "one" } else { "other" }
+    }
+}
+
+// Key, Finnish, English, Swedish. Plural messages have a ".one" and an
+// ".other" entry, {n} is replaced by the count and {0}, {1}... by arguments
+static CATALOGUE: &[(&str, &str, &str, &str)] = &[
+    ("title", "Kivi-Paperi-Sakset-Lisko-Vampyyri", "Rock-Paper-Scissors-Lizard-Vampire", "Sten-Sax-Påse-Ödla-Vampyr"),
+    ("mode.rps", "KPS", "RPS", "SSP"),
+    ("mode.rpssl", "KPSLV", "RPSLV", "SSPÖV"),
+
+    // Cards
+    ("card.games", "Pelaamaan", "Play", "Spela"),
+    ("card.schedule", "Ohjelma", "Schedule", "Spelschema"),
+    ("card.players", "Pelaajat", "Players", "Spelare"),
+    ("card.rules", "Säännöt", "Rules", "Regler"),
+    ("card.scoring", "Pisteytys", "Scoring", "Poäng"),
+    ("card.crosstable", "Ristitaulukko", "Crosstable", "Korstabell"),
+    ("card.chart", "Kehitys", "Progress", "Utveckling"),
+    ("card.season", "Kausi", "Season", "Säsong"),
+    ("card.print", "Tulosteet", "Printouts", "Utskrifter"),
+    ("card.options", "Asetukset", "Settings", "Inställningar"),
+
+    // Hands and results
+    ("hand.rock", "Kivi", "Rock", "Sten"),
+    ("hand.paper", "Paperi", "Paper", "Påse"),
+    ("hand.scissors", "Sakset", "Scissors", "Sax"),
+    ("hand.lizard", "Lisko", "Lizard", "Ödla"),
+    ("hand.vampire", "Vampyyri", "Vampire", "Vampyr"),
+    ("result.win", "Voitto", "Win", "Vinst"),
+    ("result.draw", "Tasapeli", "Draw", "Oavgjort"),
+    ("result.loss", "Tappio", "Loss", "Förlust"),
+    ("points.one", "{n} piste", "{n} point", "{n} poäng"),
+    ("points.other", "{n} pistettä", "{n} points", "{n} poäng"),
+    ("points.short", "{n}p", "{n}p", "{n}p"),
+    ("vs", "Vs.", "Vs.", "Mot"),
+
+    // Players
+    ("players.human", "Ihminen", "Human", "Människa"),
+    ("players.new", "Uusi pelaaja", "New player", "Ny spelare"),
+    ("players.add", "Lisää", "Add", "Lägg till"),
+    ("players.rank", "Sija", "Rank", "Plats"),
+    ("players.name", "Pelaaja", "Player", "Spelare"),
+    ("players.played", "Ottelut", "Played", "Matcher"),
+    ("players.score", "Pisteet", "Score", "Poäng"),
+    ("players.tiebreak", "Tasapisteet", "Tiebreak", "Särskiljning"),
+
+    // Tiebreakers
+    ("tiebreaker.head_to_head", "Keskinäiset ottelut", "Head-to-head", "Inbördes möten"),
+    ("tiebreaker.wins", "Voitot", "Wins", "Vinster"),
+    ("tiebreaker.result_points", "Tulospisteet", "Result points", "Resultatpoäng"),
+    ("tiebreaker.sonneborn_berger", "Sonneborn-Berger", "Sonneborn-Berger", "Sonneborn-Berger"),
+    ("tiebreaker.fewest_played", "Vähiten otteluita", "Fewest played", "Minst spelade"),
+
+    // Player statistics
+    ("stats.hand", "Käsi", "Hand", "Hand"),
+    ("stats.thrown", "Heitot", "Thrown", "Kast"),
+    ("stats.wins", "Voitot", "Wins", "Vinster"),
+    ("stats.draws", "Tasapelit", "Draws", "Oavgjorda"),
+    ("stats.losses", "Tappiot", "Losses", "Förluster"),
+    ("stats.result_points", "Tulospisteet", "Result points", "Resultatpoäng"),
+    ("stats.hand_points", "Käsipisteet", "Hand points", "Handpoäng"),
+    ("stats.streak", "Putki", "Streak", "Svit"),
+    ("stats.longest_win_streak", "Pisin voittoputki", "Longest winning streak", "Längsta vinstsvit"),
+    ("stats.longest_loss_streak", "Pisin tappioputki", "Longest losing streak", "Längsta förlustsvit"),
+    ("stats.after_win", "Voiton jälkeen", "After a win", "Efter vinst"),
+    ("stats.after_loss", "Tappion jälkeen", "After a loss", "Efter förlust"),
+    ("streak.win.one", "{n} voitto", "{n} win", "{n} vinst"),
+    ("streak.win.other", "{n} voittoa", "{n} wins", "{n} vinster"),
+    ("streak.draw.one", "{n} tasapeli", "{n} draw", "{n} oavgjord"),
+    ("streak.draw.other", "{n} tasapeliä", "{n} draws", "{n} oavgjorda"),
+    ("streak.loss.one", "{n} tappio", "{n} loss", "{n} förlust"),
+    ("streak.loss.other", "{n} tappiota", "{n} losses", "{n} förluster"),
+
+    // Charts and scoring
+    ("chart.rank", "Sijoitus", "Rank", "Placering"),
+    ("chart.score", "Pisteet", "Score", "Poäng"),
+    ("equilibrium.title", "Tasapainostrategia", "Equilibrium strategy", "Jämviktsstrategi"),
+    ("equilibrium.probability", "Todennäköisyys", "Probability", "Sannolikhet"),
+    ("equilibrium.expected", "Odotusarvo", "Expected score", "Väntevärde"),
+    ("equilibrium.value", "Odotettu pistemäärä ottelussa", "Expected score per match", "Väntad poäng per match"),
+
+    // Rules
+    ("rules.round_robin_rps", "KPS - kaikki vastaan sarja", "RPS round robin", "SSP - alla mot alla"),
+    ("rules.round_robin", "Kaikki vastaan kaikki sarja", "Round robin", "Alla mot alla"),
+    ("rules.one_at_a_time", "Yksi peli kerrallaan", "One game at a time", "En match i taget"),
+    ("rules.rounds", "n-kertainen sarja", "n rounds", "n omgångar"),
+    ("rules.result_points", "Pisteitä saa tuloksesta", "Points are awarded for the result", "Poäng ges för resultatet"),
+    ("rules.hand_points", "ja pelatusta kädestä (riippumatta tuloksesta)", "and for the hand played (regardless of the result)", "och för den spelade handen (oavsett resultat)"),
+    ("rules.winner", "Eniten pisteitä kerännyt on voittaja", "The player with the most points wins", "Den med flest poäng vinner"),
+    ("rules.tiebreakers", "Tasapisteissä järjestyksen ratkaisevat, tässä järjestyksessä:", "Equal scores are settled by, in this order:", "Vid lika poäng avgör, i denna ordning:"),
+    ("rules.no_tiebreakers", "Tasapisteissä pelaajat jakavat sijoituksen", "Players with equal scores share their rank", "Spelare med lika poäng delar placering"),
+    ("rule.value", "{0}: {1}", "{0}: {1}", "{0}: {1}"),
+    ("verbs.scissors.paper", "leikkaa", "cuts", "klipper"),
+    ("verbs.paper.rock", "peittää", "covers", "täcker"),
+    ("verbs.rock.lizard", "murskaa", "crushes", "krossar"),
+    ("verbs.lizard.vampire", "myrkyttää", "poisons", "förgiftar"),
+    ("verbs.vampire.scissors", "rikkoo", "smashes", "krossar"),
+    ("verbs.scissors.lizard", "mestaa", "decapitates", "halshugger"),
+    ("verbs.lizard.paper", "syö", "eats", "äter"),
+    ("verbs.paper.vampire", "kumoaa", "disproves", "motbevisar"),
+    ("verbs.vampire.rock", "höyrystää", "vaporizes", "förångar"),
+    ("verbs.rock.scissors", "murskaa", "crushes", "krossar"),
+    // Hands are emoji, so Finnish needs no inflected objects
+    ("commentary.win", "{1} {2} {4}! {0} voittaa, {3} häviää", "{0}'s {1} {2} {3}'s {4}", "{0}s {1} {2} {3}s {4}"),
+    ("commentary.draw", "{0} ja {1} heittävät molemmat {2}, tasapeli", "{0} and {1} both throw {2}, a draw", "{0} och {1} kastar båda {2}, oavgjort"),
+    ("commentary.lead", "{0} nousee kärkeen!", "{0} takes the lead!", "{0} tar ledningen!"),
+    ("commentary.leader_beaten", "Kärjessä ollut {0} kaatuu!", "The leader {0} falls!", "Ledaren {0} faller!"),
+    ("commentary.upset", "Yllätys!", "Upset!", "Skräll!"),
+    ("beats.scissors.paper", "Sakset leikkaavat paperin", "Scissors cuts Paper", "Sax klipper påse"),
+    ("beats.paper.rock", "Paperi peittää kiven", "Paper covers Rock", "Påse täcker sten"),
+    ("beats.rock.lizard", "Kivi murskaa liskon", "Rock crushes Lizard", "Sten krossar ödla"),
+    ("beats.lizard.vampire", "Lisko myrkyttää vampyyrin", "Lizard poisons Vampire", "Ödla förgiftar vampyr"),
+    ("beats.vampire.scissors", "Vampyyri rikkoo sakset", "Vampire smashes Scissors", "Vampyr krossar sax"),
+    ("beats.scissors.lizard", "Sakset katkaisevat liskon pään", "Scissors decapitates Lizard", "Sax halshugger ödla"),
+    ("beats.lizard.paper", "Lisko syö paperin", "Lizard eats Paper", "Ödla äter påse"),
+    ("beats.paper.vampire", "Paperi kumoaa vampyyrin", "Paper disproves Vampire", "Påse motbevisar vampyr"),
+    ("beats.vampire.rock", "Vampyyri höyrystää kiven", "Vampire vaporizes Rock", "Vampyr förångar sten"),
+    ("beats.rock.scissors", "Kivi murskaa sakset", "Rock crushes Scissors", "Sten krossar sax"),
+
+    // Playing
+    ("play.saving", "Tallennetaan...", "Saving...", "Sparar..."),
+    ("play.undo", "Peru", "Undo", "Ångra"),
+    ("play.play", "Pelaa", "Play", "Spela"),
+    ("play.on_deck", "Valmistautumassa", "On deck", "Nästa på tur"),
+    ("games_left.one", "{n} peli jäljellä", "{n} game left", "{n} match kvar"),
+    ("games_left.other", "{n} peliä jäljellä", "{n} games left", "{n} matcher kvar"),
+
+    // Schedule
+    ("schedule.now", "Nyt", "Now", "Nu"),
+    ("schedule.on_deck", "Seuraavaksi", "Next", "Därefter"),
+    ("schedule.round", "{0}. kierros", "Round {0}", "Omgång {0}"),
+    ("schedule.pin", "Kärkeen", "To the front", "Först"),
+    ("schedule.push_back", "Loppuun", "To the back", "Sist"),
+    ("schedule.away", "Poissa", "Away", "Borta"),
+
+    // Season
+    ("season.name", "Kausi", "Season", "Säsong"),
+    ("season.points", "Pisteet", "Points", "Poäng"),
+    ("season.wins", "Voitot", "Wins", "Vinster"),
+    ("season.best", "Paras", "Best", "Bästa"),
+    ("season.attended", "Läsnä", "Attended", "Närvaro"),
+    ("season.present", "Mukana", "Playing", "Med"),
+    ("season.placing_points", "Sijoituspisteet", "Points by placing", "Placeringspoäng"),
+    ("season.add_current", "Lisää nykyinen turnaus kauteen", "Add the current tournament to the season", "Lägg till nuvarande turnering i säsongen"),
+    ("season.new_tournament", "Uusi turnaus", "New tournament", "Ny turnering"),
+
+    // Settings
+    ("settings.title", "Asetukset", "Settings", "Inställningar"),
+    ("settings.mode_change", "Vaihdetaanko pelimuodoksi {0}?", "Change the game mode to {0}?", "Byta spelläge till {0}?"),
+    ("settings.mode_rescore", "Kaikkien pelattujen otteluiden pisteet lasketaan uudelleen uuden pelimuodon mukaan.", "Scores of all played matches are counted again with the new scoring.", "Poängen för alla spelade matcher räknas om enligt det nya spelläget."),
+    ("settings.mode_void", "Näiden otteluiden tulokset mitätöidään, koska niiden käsiä ei ole uudessa pelimuodossa:", "Results of these matches are voided, because the new game mode doesn't have their hands:", "Resultaten av dessa matcher stryks, eftersom det nya spelläget inte har deras händer:"),
+    ("settings.round_discard", "Kierroksen poistaminen hävittää näiden otteluiden tulokset:", "Removing the round discards the results of these matches:", "Att ta bort omgången raderar resultaten av dessa matcher:"),
+    ("settings.empty_confirm", "Poistetaanko kaikki pelaajat ja tulokset? Tätä ei voi perua.", "Remove all players and results? This can't be undone.", "Ta bort alla spelare och resultat? Det går inte att ångra."),
+    ("settings.confirm", "Vahvista", "Confirm", "Bekräfta"),
+    ("settings.cancel", "Peruuta", "Cancel", "Avbryt"),
+    ("settings.mode", "Peli", "Game", "Spel"),
+    ("settings.seed", "Siemen", "Seed", "Frö"),
+    ("settings.rounds", "Kierroksia", "Rounds", "Omgångar"),
+    ("settings.tiebreakers", "Tasapisteiden ratkaisu", "Tiebreakers", "Särskiljning"),
+    ("settings.language", "Kieli", "Language", "Språk"),
+    ("match.unplayed", "Ei vielä pelattu", "Not played yet", "Inte spelad ännu"),
+    ("match.commitment", "sitoumus", "commitment", "åtagande"),
+    ("match.back", "Takaisin otteluihin", "Back to the matches", "Tillbaka till matcherna"),
+    ("remote.enable", "Pelaajat heittävät omilla laitteillaan", "Players throw on their own devices", "Spelarna kastar på egna enheter"),
+    ("remote.links", "Pelaajien linkit", "Player links", "Spelarnas länkar"),
+    ("remote.secret", "Heittopalvelimen ylläpitäjän salasana", "Operator secret of the throw server", "Kastserverns operatörslösenord"),
+    ("remote.waiting", "Odotetaan kättä", "Waiting for a hand", "Väntar på en hand"),
+    ("remote.locked", "Käsi lukittu", "Hand locked in", "Handen låst"),
+    ("remote.revealed", "Käsi paljastettu", "Hand revealed", "Handen avslöjad"),
+    ("remote.wait_turn", "Odota vuoroasi", "Wait for your turn", "Vänta på din tur"),
+    ("remote.offline", "Heittopalvelimeen ei saada yhteyttä", "Cannot reach the throw server", "Kastservern kan inte nås"),
+    ("profile.nickname", "Lempinimi", "Nickname", "Smeknamn"),
+    ("profile.colour", "Väri", "Colour", "Färg"),
+    ("profile.emoji", "Emoji", "Emoji", "Emoji"),
+    ("profile.upload", "tai kuva", "or picture", "eller bild"),
+    ("profile.catchphrase", "Tunnuslause", "Catchphrase", "Slogan"),
+    ("profile.clear", "Poista", "Remove", "Ta bort"),
+    ("profile.save", "Tallenna profiili", "Save profile", "Spara profil"),
+    ("print.match", "Ottelu", "Match", "Match"),
+    ("print.sheets", "Ottelulaput", "Match sheets", "Matchblad"),
+    ("print.crosstable_file", "ristitaulukko.html", "crosstable.html", "korstabell.html"),
+    ("print.sheets_file", "ottelulaput.html", "match-sheets.html", "matchblad.html"),
+    ("print.download_crosstable", "Lataa ristitaulukko tulostettavaksi", "Download the crosstable for printing", "Ladda ner korstabellen för utskrift"),
+    ("print.download_sheets", "Lataa ottelulaput tulostettavaksi", "Download match sheets for printing", "Ladda ner matchblad för utskrift"),
+    ("print.bulk", "Tulokset paperilta", "Results from paper", "Resultat från papper"),
+    ("print.bulk_help", "Yksi ottelu riville: ottelun koodi ja kummankin pelaajan käden kirjain, esim. \"1-2-1 r p\". Botille merkitään \"-\".", "One match per line: the match code and the letter of each player's hand, e.g. \"1-2-1 r p\". Bots get \"-\".", "En match per rad: matchens kod och bokstaven för varje spelares hand, t.ex. \"1-2-1 r p\". Botar får \"-\"."),
+    ("print.bulk_add", "Lisää tulokset", "Add results", "Lägg till resultat"),
+    ("print.bulk_line", "Rivi {0}: {1}", "Line {0}: {1}", "Rad {0}: {1}"),
+    ("print.bulk_added.one", "{n} tulos lisätty", "{n} result added", "{n} resultat tillagt"),
+    ("print.bulk_added.other", "{n} tulosta lisätty", "{n} results added", "{n} resultat tillagda"),
+    ("quote.general", "Yleinen", "General", "Allmänt"),
+    ("quote.start", "Turnauksen alku", "Tournament start", "Turneringens början"),
+    ("quote.upset", "Yllätys", "Upset", "Skräll"),
+    ("quote.leader_beaten", "Kärki kaatui", "Leader beaten", "Ledaren slagen"),
+    ("quote.draw", "Tasapeli", "Draw", "Oavgjort"),
+    ("quote.final", "Viimeinen ottelu", "Final match", "Sista matchen"),
+    ("quote.own", "Omat lainaukset", "Own quotes", "Egna citat"),
+    ("quote.text", "Lainaus", "Quote", "Citat"),
+    ("quote.author", "Kuka sanoi", "Author", "Upphovsperson"),
+    ("quote.add", "Lisää lainaus", "Add quote", "Lägg till citat"),
+    ("countdown.beat1", "Kivi!", "Rock!", "Sten!"),
+    // The Swedish rhyme goes rock, scissors, paper
+    ("countdown.beat2", "Paperi!", "Paper!", "Sax!"),
+    ("countdown.beat3", "Sakset!", "Scissors!", "Påse!"),
+    ("countdown.locked", "Kädet lukittu", "Hands locked", "Händerna låsta"),
+    ("countdown.start", "Aloita lähtölaskenta", "Start the countdown", "Starta nedräkningen"),
+    ("countdown.off", "Ei", "Off", "Av"),
+    ("countdown.on", "Käytössä", "On", "På"),
+    ("countdown.tempo", "Tahti", "Tempo", "Takt"),
+    ("countdown.penalty", "Myöhästyminen", "Late hand", "Sen hand"),
+    ("countdown.loss", "Automaattinen tappio", "Automatic loss", "Automatisk förlust"),
+    ("countdown.random", "Satunnainen käsi", "Random hand", "Slumpad hand"),
+    ("projector.latest", "Viimeisimmät", "Latest results", "Senaste resultat"),
+    ("settings.countdown", "Lähtölaskenta", "Countdown", "Nedräkning"),
+    ("settings.projector", "Avaa katsojanäkymä", "Open the spectator view", "Öppna åskådarvyn"),
+    ("settings.debug", "Testipeli", "Debug", "Testspel"),
+    ("settings.empty", "Tyhjennä", "Empty", "Töm"),
+
+    // Errors
+    ("error.player_exists", "Pelaaja {0} on jo olemassa", "Player {0} already exists", "Spelaren {0} finns redan"),
+    ("error.match_exists", "Ottelu on jo olemassa", "Match already exists", "Matchen finns redan"),
+    ("error.no_such_game", "Ottelua ei ole", "No such game", "Matchen finns inte"),
+    ("error.no_such_player", "Pelaajaa ei ole", "No such player", "Spelaren finns inte"),
+    ("error.no_such_tournament", "Turnausta ei ole", "No such tournament", "Turneringen finns inte"),
+    ("error.not_played", "Ottelua ei ole pelattu", "Game has not been played", "Matchen har inte spelats"),
+    ("error.already_played", "Ottelu on jo pelattu", "Game has already been played", "Matchen har redan spelats"),
+    ("error.missing_hands", "Molemmat kädet tarvitaan", "Both hands are needed", "Båda händerna behövs"),
+    ("error.incompatible_results.one", "{n} pelatussa ottelussa on käsi, jota uudessa pelimuodossa ei ole", "{n} played match has a hand the new game mode doesn't have", "{n} spelad match har en hand som inte finns i det nya spelläget"),
+    ("error.incompatible_results.other", "{n} pelatussa ottelussa on käsi, jota uudessa pelimuodossa ei ole", "{n} played matches have a hand the new game mode doesn't have", "{n} spelade matcher har en hand som inte finns i det nya spelläget"),
+    ("error.round_played.one", "Viimeisellä kierroksella on {n} pelattu ottelu", "The last round has {n} played match", "Den sista omgången har {n} spelad match"),
+    ("error.round_played.other", "Viimeisellä kierroksella on {n} pelattua ottelua", "The last round has {n} played matches", "Den sista omgången har {n} spelade matcher"),
+    ("error.results_exist", "Poista pelatut ottelut ennen pelimuodon vaihtoa", "Remove played games before changing game mode", "Ta bort spelade matcher innan spelläget byts"),
+    ("error.min_rounds", "Kierroksia on oltava vähintään yksi", "There must be at least one round", "Det måste finnas minst en omgång"),
+    ("error.too_few_bots", "Tarvitaan vähintään kaksi bottia", "At least two bots are needed", "Minst två bottar behövs"),
+    ("error.invalid_hand", "Kättä ei ole tässä pelimuodossa", "Hand is not part of this game mode", "Handen finns inte i det här spelläget"),
+    ("error.already_thrown", "Käsi on jo heitetty", "Hand has already been thrown", "Handen har redan kastats"),
+    ("error.not_committed", "Molempien pelaajien on ensin lukittava kätensä", "Both players must lock in their hands first", "Båda spelarna måste först låsa sina händer"),
+    ("error.too_late", "Käsi tuli lähtölaskennan jälkeen", "Hand came after the countdown", "Handen kom efter nedräkningen"),
+    ("error.bad_token", "Linkki ei ole tämän pelaajan", "This link does not belong to the player", "Länken tillhör inte spelaren"),
+    ("error.not_operator", "Väärä ylläpitäjän salasana", "Wrong operator secret", "Fel operatörslösenord"),
+    ("error.invalid_colour", "Väri ei kelpaa", "Invalid colour", "Ogiltig färg"),
+    ("error.invalid_avatar", "Kuvan pitää olla alle 64 kt:n kuvatiedosto", "The picture must be an image file under 64 kB", "Bilden måste vara en bildfil under 64 kB"),
+    ("error.unreadable_line", "Riviä ei voi lukea", "Line can't be read", "Raden kan inte läsas"),
+    ("error.empty_quote", "Lainaus ei voi olla tyhjä", "Quote can't be empty", "Citatet kan inte vara tomt"),
+    ("error.no_such_quote", "Lainausta ei löydy", "No such quote", "Citatet finns inte"),
+    ("error.bad_commitment", "Paljastettu käsi ei vastaa lukittua", "Revealed hand does not match the locked one", "Den avslöjade handen matchar inte den låsta"),
+];
+
+// Message for a key built at runtime, None if it is missing
+pub fn lookup(language: Language, key: &str) -> Option<&'static str> {
+    let (_, fi, en, sv) = CATALOGUE.iter().find(|(k, ..)| *k == key)?;
+    Some(match language {
+        Language::Fi => fi,
+        Language::En => en,
+        Language::Sv => sv,
+    })
+}
+
+// Message for key, or the key itself if it is missing from the catalogue
+pub fn tr(language: Language, key: &'static str) -> &'static str {
+    lookup(language, key).unwrap_or(key)
+}
+
+// Message with {0}, {1}... replaced by the arguments
+pub fn tr_args(language: Language, key: &'static str, args: &[&str]) -> String {
+    args.iter().enumerate().fold(tr(language, key).to_string(), |message, (i, arg)| {
+        message.replace(&format!("{{{}}}", i), arg)
+    })
+}
+
+// Plural message for the count n
+pub fn tr_n(language: Language, key: &str, n: i64) -> String {
+    let plural_key = format!("{}.{}", key, language.plural(n));
+    let message = lookup(language, &plural_key)
+        .or_else(|| lookup(language, key))
+        .unwrap_or(key);
+    message.replace("{n}", &n.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, GameMode};
+    use crate::error::GameError;
+    use std::collections::HashSet;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn catalogue() {
+        let keys = CATALOGUE.iter().map(|(k, ..)| *k).collect::<HashSet<_>>();
+        assert_eq!(keys.len(), CATALOGUE.len());
+        for (key, fi, en, sv) in CATALOGUE {
+            assert!(!fi.is_empty() & !en.is_empty() & !sv.is_empty(), "{}", key);
+            if let Some(base) = key.strip_suffix(".one") {
+                assert!(keys.contains(format!("{}.other", base).as_str()), "{}", key);
+            }
+        }
+        for language in Language::iter() {
+            assert_eq!(Language::from_code(language.code()), Some(language));
+        }
+        for beat in GameMode::RPSSL.rules().beats {
+            assert!(lookup(Language::Sv, &beat.key()).is_some(), "{}", beat.key());
+        }
+
+        assert_eq!(tr(Language::En, "card.players"), "Players");
+        assert_eq!(tr(Language::Fi, "missing.key"), "missing.key");
+        assert_eq!(tr_n(Language::Fi, "games_left", 1), "1 peli jäljellä");
+        assert_eq!(tr_n(Language::Fi, "games_left", 3), "3 peliä jäljellä");
+        assert_eq!(tr_n(Language::En, "games_left", 0), "0 games left");
+        assert_eq!(tr_args(Language::Sv, "schedule.round", &["2"]), "Omgång 2");
+
+        let mut game = Game::with_seed(1);
+        let _ = game.add_player("Alice");
+        let error = game.add_player("Alice").unwrap_err();
+        assert_eq!(error, GameError::PlayerExists("Alice".to_string()));
+        assert_eq!(error.localized(Language::Fi), "Pelaaja Alice on jo olemassa");
+        assert_eq!(error.to_string(), "Player Alice already exists");
+    }
+}
@@ -1,10 +1,11 @@
 #[macro_use]
 extern crate num_derive;
 use num_traits::FromPrimitive;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use priority_queue::PriorityQueue;
-use std::cmp::Reverse;
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Serialize, Deserialize};
 use std::fmt::Display;
 use core::fmt;
 use log::info;
@@ -12,42 +13,36 @@ use log::debug;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-fn get_quote(i: usize) -> (String, String) {
-    let quotes: Vec<(&str, &str)> = vec![
-        ("Victory belongs to the most persevering.", "Napoleon Bonaparte"),
-        ("Your victory is right around the corner. Never give up.", "Nicky Minaj"),
-        ("War is a series of catastrophes which result in victory.", "Albert Pike"),
-        ("Know thy self, know thy enemy. A thousand battles, a thousand victories", "Sun Tzu"),
-        ("No victory without suffering", "J. R. R. Tolkien"),
-        ("Victory comes from finding opportunities in problems", "Sun Tzu"),
-        ("Without training, they lacked knowledge. Without knowledge, they lacked confidence. Without confidence, they lacked victory.", "Julius Ceasar"),
-        ("The will to conquer is the first condition of victory", "Ferdinand Foch"),
-        ("The more difficult the victory, the greater the happiness in winning", "Pele"), //TODO!
-        ("Preparedness is the key to success and victory", "Douglas MacArthur"),
-        ("So a military force has no constant formation, water has no constant shape: the ability to gain victory by changing and adapting according to the opponent is called genius.", "Sun Tzu"),
-        ("Even the smallest victory is never to be taken for granted. Each victory must be applauded...", "Audre Lorde"),
-        ("There is only one decisive victory: the last.","Carl von Clausewitz"),
-        ("Victory is sweetest when you've known defeat.", "Malcolm Forbes"),
-        ("You ask what the aim is? I tell you it is victory - total victory.", "Winston Churchill "),
-        ("Forewarned, forearmed; to be prepared is half the victory.","Miguel de Cervantes"),
-        ("Full effort is full victory.","Mahatma Gandhi"),
-        ("If there exists no possibility of failure, then victory is meaningless.","Robert H. Schuller"),
-        ("We lost because we told ourselves we lost.","Leo Tolstoy"),
-        ("The secret of all victory lies in the organization of the non-obvious.", "Marcus Aurelius"),
-        ("I would challenge you to a battle of wits, but I see you are unarmed!","William Shakespeare"),
-        ("Sometimes by losing a battle you find a new way to win the war.","Donald Trump"),
-        ("All men can see these tactics whereby I conquer, but what none can see is the strategy out of which victory is evolved.","Sun Tzu"),
-        ("Our greatest glory is not in never falling, but in rising every time we fall.", "Confucius"),
-        ("Somebody's gotta win and somebody's gotta lose and I believe in letting the other guy lose.", "Pete Rose"),
-    ];
-    let q = quotes[i % quotes.len()];
-    (q.0.to_string(), q.1.to_string())
-}
+pub mod analysis;
+pub mod commentary;
+pub mod commit;
+pub mod countdown;
+pub mod error;
+pub mod i18n;
+pub mod print;
+pub mod profile;
+pub mod quotes;
+pub mod rules;
+pub mod schedule;
+pub mod remote;
+pub mod season;
+pub mod sim;
+pub mod standings;
+pub mod stats;
+pub mod strategy;
+
+use commit::Commitment;
+use countdown::Countdown;
+use error::GameError;
+use profile::Profile;
+use quotes::Quote;
+use standings::Tiebreaker;
+use strategy::StrategyKind;
 
 //static WINSCORE: u16 = 10;
 //static DRAWSCORE: u16 = 5;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameMode {
     RPS,
     RPSSL,
@@ -60,6 +55,47 @@ impl GameMode {
             Self::RPSSL => "RPSSL",
         }
     }
+
+    // Hands that can be played in this mode
+    pub fn hands(&self) -> Vec<Rpssl> {
+        match self {
+            Self::RPS => vec![Rpssl::Rock, Rpssl::Paper, Rpssl::Scissors],
+            Self::RPSSL => Rpssl::iter().filter(|h| h.is_some()).collect(),
+        }
+    }
+}
+
+// Every state changing call on Game is recorded as an Action,
+// so that a game can be saved and replayed exactly
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    AddPlayer(String),
+    AddBot(String, StrategyKind),
+    SetMode(GameMode),
+    ChangeMode(GameMode),
+    SetRounds(usize),
+    SetTiebreakers(Vec<Tiebreaker>),
+    AddResult((u16, u16, u16), Rpssl, Rpssl),
+    AddResultWithBots((u16, u16, u16), Rpssl, Rpssl),
+    RemoveResult((u16, u16, u16)),
+    EditResult((u16, u16, u16), Rpssl, Rpssl),
+    AddCommittedResult((u16, u16, u16), Option<Commitment>, Option<Commitment>),
+    SetCountdown(Option<Countdown>),
+    AddTimedResult((u16, u16, u16), Option<Rpssl>, Option<Rpssl>),
+    RemoveLatest,
+    PinMatch((u16, u16, u16)),
+    PushBack((u16, u16, u16)),
+    SetAway(u16, bool),
+    SetProfile(u16, Profile),
+    AddQuote(Quote),
+    RemoveQuote(usize),
+    Empty,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub seed: u64,
+    pub actions: Vec<Action>,
 }
 
 #[derive(Debug, Clone)]
@@ -68,9 +104,22 @@ pub struct Game {
     // keys are player1, player2, round
     pub match_list: BTreeMap<(u16, u16, u16), Match>,
     queue: PriorityQueue<(u16, u16, u16), i64>,
-    rng_seed: usize,
+    seed: u64,
+    // All random decisions are drawn from this
+    rng: ChaCha8Rng,
+    quote_index: usize,
+    actions: Vec<Action>,
+    tiebreakers: Vec<Tiebreaker>,
+    // Matches moved to the front of the queue, latest pin first
+    pins: Vec<(u16, u16, u16)>,
+    // Matches moved to the back of the queue
+    deferred: BTreeSet<(u16, u16, u16)>,
     rounds: usize,
     game_mode: GameMode,
+    // Timed reveal, None when hands are entered without a countdown
+    countdown: Option<Countdown>,
+    // Organisers' own quotes
+    quotes: Vec<Quote>,
 }
 
 
@@ -82,13 +131,96 @@ impl Default for Game {
 
 impl Game {
     pub fn new() -> Game {
+        Self::with_seed(rand::thread_rng().gen())
+    }
+
+    pub fn with_seed(seed: u64) -> Game {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let quote_index = rng.gen_range(0..100);
         Game {
             player_list: BTreeMap::new(),
             match_list: BTreeMap::new(),
             queue: PriorityQueue::new(),
-            rng_seed: rand::thread_rng().gen_range(0..100),
+            seed,
+            rng,
+            quote_index,
+            actions: Vec::new(),
+            tiebreakers: Tiebreaker::default_chain(),
+            pins: Vec::new(),
+            deferred: BTreeSet::new(),
             rounds: 1,
             game_mode: GameMode::RPS,
+            countdown: None,
+            quotes: Vec::new(),
+        }
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn save(&self) -> SavedGame {
+        SavedGame {seed: self.seed, actions: self.actions.clone()}
+    }
+
+    // Rebuild a game by replaying the saved actions with the saved seed
+    pub fn from_saved(saved: &SavedGame) -> Result<Game, GameError> {
+        let mut game = Game::with_seed(saved.seed);
+        for action in &saved.actions {
+            game.apply(action.clone())?;
+        }
+        Ok(game)
+    }
+
+    fn apply(&mut self, action: Action) -> Result<(), GameError> {
+        match action {
+            Action::AddPlayer(name) => self.add_player(&name),
+            Action::AddBot(name, strategy) => self.add_bot(&name, strategy),
+            Action::SetMode(mode) => self.set_mode(mode),
+            Action::ChangeMode(mode) => self.change_mode(mode, true).map(|_| ()),
+            Action::SetRounds(rounds) => {
+                self.set_rounds(rounds);
+                Ok(())
+            },
+            Action::SetTiebreakers(tiebreakers) => {
+                self.set_tiebreakers(tiebreakers);
+                Ok(())
+            },
+            Action::AddResult(game_id, play1, play2) => {
+                if !self.match_list.contains_key(&game_id) {
+                    return Err(GameError::NoSuchGame);
+                }
+                self.add_result(game_id, play1, play2);
+                Ok(())
+            },
+            Action::AddResultWithBots(game_id, play1, play2) => {
+                if !self.match_list.contains_key(&game_id) {
+                    return Err(GameError::NoSuchGame);
+                }
+                self.add_result_with_bots(game_id, play1, play2);
+                Ok(())
+            },
+            Action::RemoveResult(game_id) => self.remove_result(game_id),
+            Action::EditResult(game_id, play1, play2) => self.edit_result(game_id, play1, play2),
+            Action::AddCommittedResult(game_id, commitment1, commitment2) => {
+                self.add_committed_result(game_id, commitment1, commitment2)
+            },
+            Action::SetCountdown(countdown) => {
+                self.set_countdown(countdown);
+                Ok(())
+            },
+            Action::AddTimedResult(game_id, play1, play2) => self.add_timed_result(game_id, play1, play2),
+            Action::RemoveLatest => {
+                self.remove_latest();
+                Ok(())
+            },
+            Action::PinMatch(game_id) => self.pin_match(game_id),
+            Action::PushBack(game_id) => self.push_back(game_id),
+            Action::SetAway(pid, away) => self.set_away(pid, away),
+            Action::SetProfile(pid, profile) => self.set_profile(pid, profile),
+            Action::AddQuote(quote) => self.add_quote(quote),
+            Action::RemoveQuote(index) => self.remove_quote(index),
+            Action::Empty => self.empty(),
         }
     }
 
@@ -114,11 +246,17 @@ impl Game {
             player.score = score;
         }
     }
+    // Players in standings order, see get_standings
     pub fn get_scores(&mut self) -> Vec<&Player> {
         self.update_scores();
-        let mut sorted_scores = self.player_list.values().collect::<Vec<_>>();
-        sorted_scores.sort_by_key(|p1| Reverse(p1.score));
-        sorted_scores
+        self.get_standings().iter()
+            .map(|s| self.player_list.get(&s.pid).expect("Key should exist"))
+            .collect()
+    }
+
+    pub fn set_tiebreakers(&mut self, tiebreakers: Vec<Tiebreaker>) {
+        self.tiebreakers = tiebreakers.clone();
+        self.actions.push(Action::SetTiebreakers(tiebreakers));
     }
 
     fn get_score_for_player(&self, pid: u16) -> (u16, u16) {
@@ -130,22 +268,51 @@ impl Game {
         )
     }
 
-    pub fn set_mode(&mut self, game_mode: GameMode) -> Result<(), String>{
+    pub fn set_mode(&mut self, game_mode: GameMode) -> Result<(), GameError>{
         if self.get_played_n() > 0 {
-            Err(String::from("Remove played games before changing game mode"))
+            Err(GameError::ResultsExist)
         } else {
             self.game_mode = game_mode;
+            self.actions.push(Action::SetMode(game_mode));
             Ok(())
         }
     }
 
+    // Played matches with a hand that doesn't exist in the given mode
+    pub fn get_mode_conflicts(&self, game_mode: GameMode) -> Vec<(u16, u16, u16)> {
+        let hands = game_mode.hands();
+        self.get_played_games().iter()
+            .filter(|(m, _p)| !hands.contains(&m.play1) | !hands.contains(&m.play2))
+            .map(|(m, _p)| (m.player1, m.player2, m.round))
+            .collect()
+    }
+
+    // Changes the mode after results have been added. Results that can't
+    // exist in the new mode are voided if allowed, and all scores are
+    // counted again with the new scoring. Returns the voided matches
+    pub fn change_mode(&mut self, game_mode: GameMode, void_conflicts: bool) -> Result<Vec<(u16, u16, u16)>, GameError> {
+        let conflicts = self.get_mode_conflicts(game_mode);
+        if !conflicts.is_empty() & !void_conflicts {
+            return Err(GameError::IncompatibleResults(conflicts.len()));
+        }
+        for game_id in &conflicts {
+            self.clear_result(*game_id)?;
+        }
+        self.game_mode = game_mode;
+        self.actions.push(Action::ChangeMode(game_mode));
+        self.update_scores();
+        self.update_priorities();
+        Ok(conflicts)
+    }
+
     pub fn get_mode(&self) -> GameMode {
-        self.game_mode.clone()
+        self.game_mode
     }
 
     pub fn set_rounds(&mut self, rounds: usize) {
         let old_rounds = self.rounds;
         self.rounds = rounds;
+        self.actions.push(Action::SetRounds(rounds));
         if rounds > old_rounds {
             let ids = self.player_list.keys().collect::<Vec<_>>();
             for player_id in ids {
@@ -167,67 +334,72 @@ impl Game {
             }
         }
         if rounds < old_rounds {
-            let ids = self.player_list.keys().collect::<Vec<_>>();
-            for player_id in ids {
-                for (id, p) in &self.player_list {
-                    if id <= player_id {
-                        continue;
-                    }
-                    for round in (rounds+1)..=(old_rounds) {
-                        if self.match_list.contains_key(&(p.id, *player_id, round as u16)) {
-                            info!("Remove match");
-                            self.match_list.remove(&(*id, *player_id, round as u16));
-                            self.queue.remove(&(*id, *player_id, round as u16));
-                        }
-                    }
-                }
+            // Matches of players added later have their ids in either order
+            let removed = self.match_list.keys()
+                .filter(|k| k.2 as usize > rounds)
+                .copied()
+                .collect::<Vec<_>>();
+            for k in removed {
+                info!("Remove match");
+                self.match_list.remove(&k);
+                self.queue.remove(&k);
+                self.deferred.remove(&k);
             }
-            self.update_priorities();
+            self.pins.retain(|k| k.2 as usize <= rounds);
+            // Results of the removed matches no longer count
+            self.update_scores();
         }
+        self.update_priorities();
     }
 
-    pub fn get_rounds(&mut self) -> usize {
+    pub fn get_rounds(&self) -> usize {
         self.rounds
     }
 
-    pub fn add_rounds(&mut self) -> Result<(), String> {
+    pub fn add_rounds(&mut self) -> Result<(), GameError> {
         self.set_rounds(self.rounds + 1);
         Ok(())
     }
 
-    pub fn empty(&mut self) -> Result<(), String> {
+    pub fn empty(&mut self) -> Result<(), GameError> {
         self.queue = PriorityQueue::new();
         self.player_list = BTreeMap::new();
         self.match_list = BTreeMap::new();
+        self.pins = Vec::new();
+        self.deferred = BTreeSet::new();
+        self.actions.push(Action::Empty);
         Ok(())
     }
 
-    pub fn remove_rounds(&mut self) -> Result<(), String> {
-        if self.rounds > 1 {
-            self.set_rounds(self.rounds - 1);
-            return Ok(());
-        } else {
-            return Err("Player Already exists".to_string());
+    // Played matches of a round, in play order
+    pub fn get_round_results(&self, round: usize) -> Vec<(u16, u16, u16)> {
+        self.get_played_games().iter()
+            .filter(|(m, _p)| m.round as usize == round)
+            .map(|(m, _p)| (m.player1, m.player2, m.round))
+            .collect()
+    }
+
+    // Removes the last round. Its played matches are discarded only when
+    // allowed; they are returned so the caller can show what was lost
+    pub fn remove_rounds(&mut self, discard_results: bool) -> Result<Vec<(u16, u16, u16)>, GameError> {
+        if self.rounds <= 1 {
+            return Err(GameError::MinRounds);
         }
+        let results = self.get_round_results(self.rounds);
+        if !results.is_empty() & !discard_results {
+            return Err(GameError::RoundPlayed(results.len()));
+        }
+        self.set_rounds(self.rounds - 1);
+        Ok(results)
     }
 
     pub fn get_next_games(&self, n: usize) -> Vec<&Match> {
-        self.queue.clone().into_sorted_iter().filter_map(|(k, prior)| {
-            if prior > 0 {
-                Some(self.match_list.get(&k).expect("Match list should include all queue elements"))
-            } else {
-                None
-            }
-        }).take(n).collect::<Vec<_>>().clone()
+        self.get_available_games().into_iter().take(n).collect()
     }
 
+    // Next match in the schedule, skipping players who are away
     pub fn get_next_game(&self) -> Option<&Match> {
-        if self.queue.iter().all(|(_k,p)| p < &0) {
-            None
-        } else {
-            let (i,_p) = self.queue.peek()?;
-            self.match_list.get(i)
-        }
+        self.get_available_games().into_iter().next()
     }
 
     pub fn get_played_n(&self) -> usize {
@@ -240,10 +412,6 @@ impl Game {
         self.queue.len() - self.get_played_n()
     }
 
-    pub fn get_quote(&self) -> (String, String) {
-        let i = self.get_played_n();
-        get_quote(i + self.rng_seed)
-    }
     pub fn get_played_games(&self) -> Vec<(&Match, i64)> {
         self.queue.clone().into_sorted_iter().filter_map(|(k, prior)|  {
             if prior < 0 {
@@ -254,16 +422,33 @@ impl Game {
         }).collect::<Vec<_>>()
     }
 
-    pub fn add_player(&mut self, name: &str) -> Result<(), String> {
+    pub fn get_match(&self, game_id: (u16, u16, u16)) -> Option<&Match> {
+        self.match_list.get(&game_id)
+    }
+
+    pub fn add_player(&mut self, name: &str) -> Result<(), GameError> {
+        self.insert_player(name, None)?;
+        self.actions.push(Action::AddPlayer(name.to_string()));
+        Ok(())
+    }
+
+    pub fn add_bot(&mut self, name: &str, strategy: StrategyKind) -> Result<(), GameError> {
+        self.insert_player(name, Some(strategy))?;
+        self.actions.push(Action::AddBot(name.to_string(), strategy));
+        Ok(())
+    }
+
+    fn insert_player(&mut self, name: &str, bot: Option<StrategyKind>) -> Result<(), GameError> {
         if self.player_list.values().filter(|p| p.name == name).count() > 0 {
-            return Err("Player Already exists".to_string());
+            return Err(GameError::PlayerExists(name.to_string()));
         }
         let id = self.player_list.keys().max().unwrap_or(&0) + 1;
-        let player: Player = Player::new(name, id);
+        let mut player: Player = Player::new(name, id);
+        player.bot = bot;
         for (id, p) in &self.player_list {
             for round in 1..=(self.rounds) {
                 if self.match_list.contains_key(&(p.id, player.id, round as u16)) {
-                    return Err("Match Already exists".to_string());
+                    return Err(GameError::MatchExists);
                 }
                 if round % 2 == 1 {
                     let k = (*id, player.id, round as u16);
@@ -286,6 +471,35 @@ impl Game {
     }
 
     pub fn add_result(&mut self, game_id: (u16, u16, u16), play1: Rpssl, play2: Rpssl) {
+        self.set_result(game_id, play1, play2);
+        self.actions.push(Action::AddResult(game_id, play1, play2));
+    }
+
+    // Like add_result, but throws for bot players are generated by
+    // their strategy. The given play of a bot player is ignored
+    pub fn add_result_with_bots(&mut self, game_id: (u16, u16, u16), play1: Rpssl, play2: Rpssl) {
+        let (throw1, throw2) = self.bot_throws(game_id, play1, play2);
+        self.set_result(game_id, throw1, throw2);
+        self.actions.push(Action::AddResultWithBots(game_id, play1, play2));
+    }
+
+    fn bot_throws(&mut self, game_id: (u16, u16, u16), play1: Rpssl, play2: Rpssl) -> (Rpssl, Rpssl) {
+        let m = self.match_list.get(&game_id).unwrap();
+        let (p1, p2) = (m.player1, m.player2);
+        let bot1 = self.player_list.get(&p1).unwrap().bot;
+        let bot2 = self.player_list.get(&p2).unwrap().bot;
+        let throw1 = match bot1 {
+            Some(kind) => self.throw_for(kind.strategy(), p1, p2),
+            None => play1,
+        };
+        let throw2 = match bot2 {
+            Some(kind) => self.throw_for(kind.strategy(), p2, p1),
+            None => play2,
+        };
+        (throw1, throw2)
+    }
+
+    fn set_result(&mut self, game_id: (u16, u16, u16), play1: Rpssl, play2: Rpssl) {
         debug!("Adding result to game {} {} {}", game_id.0, game_id.1, game_id.2);
         let m = self.match_list.get_mut(&game_id).unwrap();
         let player1 = self.player_list.get_mut(&m.player1).unwrap();
@@ -312,6 +526,9 @@ impl Game {
         debug!("Player {} has played {} games with a total score {}", player2.name, &player2.played, &player2.score);
 
         info!("Add result for game {} - {} (round {}), {} ({} points) - {} ({} points)", name1, name2, m.round, play1, player1_score, play2, player2_score);
+        self.quote_index = self.rng.gen_range(0..100);
+        self.pins.retain(|k| *k != game_id);
+        self.deferred.remove(&game_id);
 
         self.update_priorities();
     }
@@ -326,20 +543,59 @@ impl Game {
             info!("No played games");
             return;
         };
-        let _ = self.remove_result((p1,p2, round ));
+        let _ = self.clear_result((p1,p2, round ));
+        self.actions.push(Action::RemoveLatest);
         info!("Removed latest play {} {} {}", p1, p2, round);
         self.update_scores();
         self.update_priorities();
     }
 
-    pub fn remove_result(&mut self, game_id: (u16, u16, u16)) -> Result<(), String> {
+    pub fn remove_result(&mut self, game_id: (u16, u16, u16)) -> Result<(), GameError> {
+        self.clear_result(game_id)?;
+        self.actions.push(Action::RemoveResult(game_id));
+        self.update_scores();
+        self.update_priorities();
+        Ok(())
+    }
+
+    // Corrects the hands of a played match. The match keeps its place in
+    // the play order
+    pub fn edit_result(&mut self, game_id: (u16, u16, u16), play1: Rpssl, play2: Rpssl) -> Result<(), GameError> {
+        if play1.is_none() | play2.is_none() {
+            return Err(GameError::MissingHands);
+        }
+        let hands = self.game_mode.hands();
+        if !hands.contains(&play1) | !hands.contains(&play2) {
+            return Err(GameError::InvalidHand);
+        }
         let m = match self.match_list.get_mut(&game_id) {
             Some(m) => m,
-            None => return Err("No such game".to_string()),
+            None => return Err(GameError::NoSuchGame),
+        };
+        if m.result.is_none() {
+            return Err(GameError::NotPlayed);
+        }
+        m.play1 = play1;
+        m.play2 = play2;
+        m.result = Some(play1.result(&play2));
+        // The corrected hands are not the committed ones
+        m.commitments = (None, None);
+        self.actions.push(Action::EditResult(game_id, play1, play2));
+        info!("Edited play {} {} {}", game_id.0, game_id.1, game_id.2);
+        self.update_scores();
+        self.update_priorities();
+        Ok(())
+    }
+
+    fn clear_result(&mut self, game_id: (u16, u16, u16)) -> Result<(), GameError> {
+        let m = match self.match_list.get_mut(&game_id) {
+            Some(m) => m,
+            None => return Err(GameError::NoSuchGame),
         };
         m.result = None;
         m.play1 = Rpssl::None;
         m.play2= Rpssl::None;
+        m.commitments = (None, None);
         info!("Removed play {} {}", game_id.0, game_id.1);
         Ok(())
     }
@@ -350,7 +606,9 @@ impl Game {
             info!("Player List is empty");
             return;
         }
-        let played_games = self.player_list.values().map(|p| p.played as i64).sum::<i64>() / 2 + 1;
+        // Played games get negative priorities in play order. Results can be
+        // removed from the middle, so continue below the latest one
+        let played_games = 1 - self.queue.iter().map(|(_k, p)| *p).filter(|p| *p < 0).min().unwrap_or(0);
         let n_games = (self.rounds * (self.player_list.len() - 1)) as u16;
 
         for (k, m) in &self.match_list {
@@ -392,7 +650,8 @@ impl Game {
             debug!("\tScore = {} / {}", &score1, &score2);
             let priority = score1 - potential1 + score2 - potential2 + (round as i64) * 1000;
             debug!("\tPriority = {}", &priority);
-            self.queue.change_priority(k, 99999 - priority);
+            let priority = self.scheduled_priority(*k, 99999 - priority);
+            self.queue.change_priority(k, priority);
         }
         debug!("priorities updated");
     }
@@ -406,6 +665,8 @@ pub struct Match {
     pub play2: Rpssl,
     pub result: Option<RpsResult>,
     pub round: u16,
+    // Revealed commitments of hands thrown on the players' own devices
+    pub commitments: (Option<Commitment>, Option<Commitment>),
 }
 
 impl Match {
@@ -417,6 +678,7 @@ impl Match {
             play2: Rpssl::None,
             result: None,
             round,
+            commitments: (None, None),
         }
     }
 
@@ -434,6 +696,28 @@ impl Match {
         (player1_score, player2_score)
     }
 
+    // Result from the point of view of the given player
+    pub fn get_result_for_player(&self, pid: u16) -> Option<RpsResult> {
+        let result = self.result.as_ref()?;
+        if pid == self.player1 {
+            Some(result.clone())
+        } else if pid == self.player2 {
+            Some(result.inverse())
+        } else {
+            None
+        }
+    }
+
+    pub fn get_play_for_player(&self, pid: u16) -> Option<Rpssl> {
+        if pid == self.player1 {
+            Some(self.play1)
+        } else if pid == self.player2 {
+            Some(self.play2)
+        } else {
+            None
+        }
+    }
+
     fn get_score_for_player(&self, pid: u16, game_mode: GameMode) -> u16 {
         if self.result.is_none() {
             return 0;
@@ -457,12 +741,21 @@ pub struct Player {
     pub name: String,
     pub score: u16,
     pub played: u16,
+    // Strategy of a computer player, None for humans
+    pub bot: Option<StrategyKind>,
+    // Matches of an away player are skipped until they return
+    pub away: bool,
+    pub profile: Profile,
 }
 
 #[allow(dead_code)]
 impl Player {
     fn new(name: &str, id: u16) -> Player {
-        Self {name: name.to_string(), id, score: 0, played: 0}
+        Self {name: name.to_string(), id, score: 0, played: 0, bot: None, away: false, profile: Profile::default()}
+    }
+
+    pub fn is_bot(&self) -> bool {
+        self.bot.is_some()
     }
 }
 
@@ -475,7 +768,7 @@ pub trait Playable: fmt::Display {
     fn is_some(&self) -> bool;
 }
 
-#[derive(Hash, Debug, Copy, Clone, PartialEq, Eq, FromPrimitive, EnumIter)]
+#[derive(Hash, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, EnumIter, Serialize, Deserialize)]
 pub enum Rpssl {
     Rock,
     Paper,
@@ -514,6 +807,14 @@ impl RpsResult {
             RpsResult::Lose => 0,
         }
     }
+
+    pub fn inverse(&self) -> RpsResult {
+        match self {
+            RpsResult::Win => RpsResult::Lose,
+            RpsResult::Draw => RpsResult::Draw,
+            RpsResult::Lose => RpsResult::Win,
+        }
+    }
 }
 
 impl Display for Rps {
@@ -567,9 +868,7 @@ impl Playable for Rpssl {
     fn result(&self, other: &Rpssl) -> RpsResult {
         if self == other {
             RpsResult::Draw
-        } else if other == &self.win().0 {
-            RpsResult::Lose
-        } else if other == &self.win().1 {
+        } else if (other == &self.win().0) | (other == &self.win().1) {
             RpsResult::Lose
         } else {
             RpsResult::Win
@@ -596,6 +895,35 @@ impl Rpssl {
         (FromPrimitive::from_u8((*self as u8 + 4) % 5).unwrap(),
         FromPrimitive::from_u8((*self as u8 + 2) % 5).unwrap())
     }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Rock => "Rock",
+            Self::Paper => "Paper",
+            Self::Scissors => "Scissors",
+            Self::Vampire => "Vampire",
+            Self::Lizard => "Lizard",
+            Self::None => "None",
+        }
+    }
+
+    // Keyboard shortcut used for quick result entry
+    pub fn shortcut(&self) -> char {
+        match self {
+            Self::Rock => 'r',
+            Self::Paper => 'p',
+            Self::Scissors => 's',
+            Self::Vampire => 'v',
+            Self::Lizard => 'l',
+            Self::None => '?',
+        }
+    }
+
+    pub fn from_shortcut(key: char) -> Option<Rpssl> {
+        Rpssl::iter()
+            .filter(|h| h.is_some())
+            .find(|h| h.shortcut() == key.to_ascii_lowercase())
+    }
 }
 
 impl Playable for Rps {
@@ -692,6 +1020,11 @@ mod tests {
         assert_eq!(paper.result(&rock), RpsResult::Win);
         assert_eq!(scissors.result(&rock), RpsResult::Lose);
         assert_eq!(scissors.result(&paper), RpsResult::Win);
+        assert_eq!(Rpssl::from_shortcut('V'), Some(vampire));
+        assert_eq!(Rpssl::from_shortcut('?'), None);
+        for hand in GameMode::RPSSL.hands() {
+            assert_eq!(Rpssl::from_shortcut(hand.shortcut()), Some(hand));
+        }
     }
 
     #[test]
@@ -731,9 +1064,10 @@ mod tests {
         let (g3, _p) = game.queue.pop().unwrap();
         assert_eq!(g3, (2,4 ,1));
 
+        // 1 and 3 are tied, 3 won their match
         let scores = game.get_scores();
-        assert_eq!(scores[0].id, 1);
-        assert_eq!(scores[1].id, 3);
+        assert_eq!(scores[0].id, 3);
+        assert_eq!(scores[1].id, 1);
         assert_eq!(scores[2].id, 2);
         assert_eq!(scores[3].id, 4);
     }
@@ -813,10 +1147,159 @@ mod tests {
         let played_games = game.get_played_games();
         assert_eq!(played_games.len(), 1);
 
+        // 1 and 3 are tied, 3 won their match
         let scores = game.get_scores();
         dbg!(&scores);
-        assert_eq!(scores[0].id, 1);
-        assert_eq!(scores[1].id, 3);
+        assert_eq!(scores[0].id, 3);
+        assert_eq!(scores[1].id, 1);
         assert_eq!(scores[2].id, 2);
     }
+
+    #[test]
+    fn seeded_replay() {
+        let mut game = Game::with_seed(42);
+        let _ = game.set_mode(GameMode::RPSSL);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_player("Charlie");
+        game.set_rounds(2);
+        game.add_result((1, 2, 1), Rpssl::Vampire, Rpssl::Rock);
+        game.add_result((1, 3, 1), Rpssl::Lizard, Rpssl::Lizard);
+        game.remove_latest();
+        game.add_result((2, 3, 1), Rpssl::Paper, Rpssl::Scissors);
+
+        let saved = game.save();
+        assert_eq!(saved.seed, 42);
+        let mut replayed = Game::from_saved(&saved).unwrap();
+        assert_eq!(replayed.get_seed(), 42);
+        assert_eq!(replayed.save(), saved);
+        assert_eq!(replayed.get_quote(), game.get_quote());
+        assert_eq!(replayed.get_mode(), GameMode::RPSSL);
+        assert_eq!(replayed.get_rounds(), 2);
+
+        let played = game.get_played_games().iter().map(|(m, p)| ((*m).clone(), *p)).collect::<Vec<_>>();
+        let replayed_played = replayed.get_played_games().iter().map(|(m, p)| ((*m).clone(), *p)).collect::<Vec<_>>();
+        assert_eq!(played, replayed_played);
+        assert_eq!(game.get_next_game(), replayed.get_next_game());
+
+        let scores = game.get_scores().iter().map(|p| (p.id, p.score, p.played)).collect::<Vec<_>>();
+        let replayed_scores = replayed.get_scores().iter().map(|p| (p.id, p.score, p.played)).collect::<Vec<_>>();
+        assert_eq!(scores, replayed_scores);
+    }
+
+    #[test]
+    fn edit_and_remove_result() {
+        let mut game = Game::with_seed(7);
+        let _ = game.set_mode(GameMode::RPSSL);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_player("Charlie");
+        game.add_result((1, 2, 1), Rpssl::Rock, Rpssl::Scissors);
+        game.add_result((1, 3, 1), Rpssl::Paper, Rpssl::Rock);
+        game.add_result((2, 3, 1), Rpssl::Lizard, Rpssl::Lizard);
+
+        // Bob won the first match after all
+        assert!(game.edit_result((1, 2, 1), Rpssl::Rock, Rpssl::Paper).is_ok());
+        assert!(game.edit_result((1, 2, 1), Rpssl::Rock, Rpssl::None).is_err());
+        let order = game.get_played_games().iter().map(|(m, _p)| (m.player1, m.player2)).collect::<Vec<_>>();
+        assert_eq!(order, vec![(1, 2), (1, 3), (2, 3)]);
+        assert_eq!(game.get_player(2).unwrap().score, 12 + 5 + 4);
+
+        assert!(game.remove_result((1, 3, 1)).is_ok());
+        assert_eq!(game.get_player(1).unwrap().played, 1);
+        assert_eq!(game.get_player(1).unwrap().score, 1);
+        assert_eq!(game.get_player(3).unwrap().score, 9);
+        assert_eq!(game.get_played_n(), 2);
+        assert_eq!(game.get_next_game().map(|m| (m.player1, m.player2)), Some((1, 3)));
+        assert!(game.edit_result((1, 3, 1), Rpssl::Rock, Rpssl::Rock).is_err());
+
+        // Replaying it puts it last in the play order
+        game.add_result((1, 3, 1), Rpssl::Scissors, Rpssl::Paper);
+        let order = game.get_played_games().iter().map(|(m, _p)| (m.player1, m.player2)).collect::<Vec<_>>();
+        assert_eq!(order, vec![(1, 2), (2, 3), (1, 3)]);
+
+        let replayed = Game::from_saved(&game.save()).unwrap();
+        assert_eq!(replayed.match_list, game.match_list);
+        assert_eq!(replayed.get_played_games(), game.get_played_games());
+
+        // Corrected hands must be part of the game mode
+        let mut game = Game::with_seed(7);
+        let _ = game.set_mode(GameMode::RPS);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        game.add_result((1, 2, 1), Rpssl::Rock, Rpssl::Scissors);
+        assert_eq!(game.edit_result((1, 2, 1), Rpssl::Vampire, Rpssl::Rock), Err(GameError::InvalidHand));
+        assert_eq!(game.edit_result((1, 2, 1), Rpssl::Rock, Rpssl::Lizard), Err(GameError::InvalidHand));
+        assert_eq!(game.get_match((1, 2, 1)).unwrap().play1, Rpssl::Rock);
+        assert!(game.edit_result((1, 2, 1), Rpssl::Paper, Rpssl::Scissors).is_ok());
+    }
+
+    #[test]
+    fn change_mode() {
+        let mut game = Game::with_seed(3);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_player("Charlie");
+        game.add_result((1, 2, 1), Rpssl::Rock, Rpssl::Scissors);
+        assert_eq!(game.get_player(1).unwrap().score, 6 + 1);
+        assert_eq!(game.set_mode(GameMode::RPSSL), Err(GameError::ResultsExist));
+
+        // Rock and scissors exist in both modes, only the scoring changes
+        assert_eq!(game.get_mode_conflicts(GameMode::RPSSL), vec![]);
+        assert_eq!(game.change_mode(GameMode::RPSSL, false), Ok(vec![]));
+        assert_eq!(game.get_player(1).unwrap().score, 10 + 1);
+
+        game.add_result((1, 3, 1), Rpssl::Vampire, Rpssl::Rock);
+        assert_eq!(game.get_mode_conflicts(GameMode::RPS), vec![(1, 3, 1)]);
+        assert_eq!(game.change_mode(GameMode::RPS, false), Err(GameError::IncompatibleResults(1)));
+        assert_eq!(game.get_mode(), GameMode::RPSSL);
+        assert_eq!(game.change_mode(GameMode::RPS, true), Ok(vec![(1, 3, 1)]));
+        assert_eq!(game.get_mode(), GameMode::RPS);
+        assert_eq!(game.get_played_n(), 1);
+        assert_eq!(game.get_player(1).unwrap().score, 6 + 1);
+        assert_eq!(game.get_player(3).unwrap().played, 0);
+        assert!(game.get_match((1, 3, 1)).unwrap().result.is_none());
+
+        let replayed = Game::from_saved(&game.save()).unwrap();
+        assert_eq!(replayed.match_list, game.match_list);
+        assert_eq!(replayed.get_mode(), GameMode::RPS);
+    }
+
+    #[test]
+    fn remove_played_round() {
+        let mut game = Game::with_seed(5);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        assert_eq!(game.remove_rounds(false), Err(GameError::MinRounds));
+        game.set_rounds(2);
+        game.add_result((1, 2, 1), Rpssl::Rock, Rpssl::Scissors);
+        game.add_result((2, 1, 2), Rpssl::Rock, Rpssl::Paper);
+        assert_eq!(game.get_round_results(2), vec![(2, 1, 2)]);
+
+        assert_eq!(game.remove_rounds(false), Err(GameError::RoundPlayed(1)));
+        assert_eq!(game.get_rounds(), 2);
+        assert_eq!(game.remove_rounds(true), Ok(vec![(2, 1, 2)]));
+        assert_eq!(game.get_rounds(), 1);
+        // Scores lose the discarded match too
+        assert_eq!(game.get_player(1).unwrap().played, 1);
+        assert_eq!(game.get_player(1).unwrap().score, 6 + 1);
+        assert_eq!(game.get_played_n(), 1);
+
+        // Players added after the rounds were set have odd rounds keyed
+        // the other way round
+        let mut game = Game::with_seed(5);
+        game.set_rounds(3);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_player("Charlie");
+        assert!(game.get_match((1, 2, 3)).is_some());
+        game.add_result((1, 2, 3), Rpssl::Rock, Rpssl::Scissors);
+        assert_eq!(game.remove_rounds(true), Ok(vec![(1, 2, 3)]));
+        assert_eq!(game.get_rounds(), 2);
+        assert!(game.match_list.keys().all(|k| k.2 <= 2));
+        assert_eq!(game.get_match((1, 2, 3)), None);
+        assert_eq!(game.get_played_n(), 0);
+        assert_eq!(game.get_left_n(), 6);
+        assert_eq!(game.get_player(1).unwrap().played, 0);
+    }
 }
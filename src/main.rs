@@ -1,16 +1,258 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
 use leptos::{ev::SubmitEvent, *};
 use leptos::html::Input;
-use pepulo_rps::{Game,Rpssl,Playable,GameMode};
+use leptos::leptos_dom::helpers::TimeoutHandle;
+use leptos_router::{use_location, use_navigate, use_params_map, Route, Router, Routes};
+use gloo_net::http::{Request, RequestBuilder};
+use pepulo_rps::{Game,Player,Rpssl,RpsResult,Playable,GameMode,SavedGame};
+use pepulo_rps::analysis;
+use pepulo_rps::error::GameError;
+use pepulo_rps::i18n::{lookup, tr, tr_args, tr_n, Language};
+use pepulo_rps::profile::Avatar;
+use pepulo_rps::quotes::{Quote, QuoteCategory};
+use pepulo_rps::rules::Rules;
+use pepulo_rps::commit::{hash, random_nonce};
+use pepulo_rps::countdown::{Countdown, LatePenalty};
+use pepulo_rps::remote::{Commit, OpenMatch, RemoteMatch, RemoteStatus, Reveal, API_COMMIT, API_MATCH, API_REVEAL, API_START, API_STATUS, OPERATOR_HEADER};
+use pepulo_rps::season::{Season, SavedSeason};
+use pepulo_rps::standings::Tiebreaker;
+use pepulo_rps::strategy::StrategyKind;
+use strum::IntoEnumIterator;
 use log::Level;
 use log::info;
 use log::debug;
 use itertools::Itertools;
 
+static STORAGE_KEY: &str = "pepulo_rps";
+static SEASON_STORAGE_KEY: &str = "pepulo_rps_season";
+static LANGUAGE_STORAGE_KEY: &str = "pepulo_rps_language";
+static OPERATOR_STORAGE_KEY: &str = "pepulo_rps_operator";
+static PROJECTOR_PATH: &str = "/projector";
+// How often the throw server is asked for news
+static POLL_INTERVAL: Duration = Duration::from_secs(1);
+// Refresh rate of the countdown words
+static TICK: Duration = Duration::from_millis(50);
+// Countdown tempo limits in the settings
+static BEAT_STEP: u64 = 100;
+static MIN_BEAT: u64 = 200;
+static MAX_BEAT: u64 = 2000;
+static COUNTDOWN_WORDS: [&str; 3] = ["countdown.beat1", "countdown.beat2", "countdown.beat3"];
+
 fn main() {
     _ = console_log::init_with_level(Level::Info);
-    leptos::mount_to_body(|| view! { <App/> })
+    leptos::mount_to_body(|| view! {
+        <Router>
+            <Routes>
+                <Route path=PROJECTOR_PATH view=Projector/>
+                <Route path="/throw/:pid/:token" view=RemoteThrow/>
+                <Route path="/*any" view=App/>
+            </Routes>
+        </Router>
+    })
+}
+
+// Pages of the app. Each one has its own address, so that the back
+// button works and a page can be bookmarked or shared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Page {
+    Home,
+    Players,
+    Player(u16),
+    Play,
+    Match((u16, u16, u16)),
+    Schedule,
+    Rules,
+    Scoring,
+    Crosstable,
+    Chart,
+    Season,
+    Print,
+    Settings,
+}
+
+impl Page {
+    // Unknown addresses lead to the front page
+    fn from_path(path: &str) -> Page {
+        let parts = path.split('/').filter(|p| !p.is_empty()).collect::<Vec<_>>();
+        let id = |i: usize| parts.get(i).and_then(|p| p.parse::<u16>().ok());
+        match parts.as_slice() {
+            ["players"] => Page::Players,
+            ["player", _] => id(1).map_or(Page::Players, Page::Player),
+            ["play"] => Page::Play,
+            ["match", _, _, _] => match (id(1), id(2), id(3)) {
+                (Some(p1), Some(p2), Some(round)) => Page::Match((p1, p2, round)),
+                _ => Page::Play,
+            },
+            ["schedule"] => Page::Schedule,
+            ["rules"] => Page::Rules,
+            ["scoring"] => Page::Scoring,
+            ["crosstable"] => Page::Crosstable,
+            ["chart"] => Page::Chart,
+            ["season"] => Page::Season,
+            ["print"] => Page::Print,
+            ["settings"] => Page::Settings,
+            _ => Page::Home,
+        }
+    }
+
+    fn path(&self) -> String {
+        match self {
+            Page::Home => "/".to_string(),
+            Page::Players => "/players".to_string(),
+            Page::Player(id) => format!("/player/{}", id),
+            Page::Play => "/play".to_string(),
+            Page::Match((p1, p2, round)) => format!("/match/{}/{}/{}", p1, p2, round),
+            Page::Schedule => "/schedule".to_string(),
+            Page::Rules => "/rules".to_string(),
+            Page::Scoring => "/scoring".to_string(),
+            Page::Crosstable => "/crosstable".to_string(),
+            Page::Chart => "/chart".to_string(),
+            Page::Season => "/season".to_string(),
+            Page::Print => "/print".to_string(),
+            Page::Settings => "/settings".to_string(),
+        }
+    }
+
+    // Card that shows the page
+    fn card(&self) -> Page {
+        match self {
+            Page::Player(_) => Page::Players,
+            Page::Match(_) => Page::Play,
+            page => *page,
+        }
+    }
+}
+
+fn use_page() -> Memo<Page> {
+    let location = use_location();
+    create_memo(move |_| location.pathname.with(|p| Page::from_path(p)))
+}
+
+fn use_go() -> impl Fn(Page) + Copy {
+    let navigate = store_value(use_navigate());
+    move |page: Page| navigate.with_value(|n| n(&page.path(), Default::default()))
+}
+
+fn load_game() -> Option<Game> {
+    let storage = window().local_storage().ok()??;
+    let json = storage.get_item(STORAGE_KEY).ok()??;
+    let saved: SavedGame = serde_json::from_str(&json).ok()?;
+    match Game::from_saved(&saved) {
+        Ok(g) => Some(g),
+        Err(e) => {
+            info!("Could not restore saved game: {}", e);
+            None
+        }
+    }
+}
+
+fn store_game(game: &Game) {
+    let Some(storage) = window().local_storage().ok().flatten() else {
+        return;
+    };
+    match serde_json::to_string(&game.save()) {
+        Ok(json) => {
+            let _ = storage.set_item(STORAGE_KEY, &json);
+        },
+        Err(e) => info!("Could not save game: {}", e),
+    }
+}
+
+fn load_season() -> Option<Season> {
+    let storage = window().local_storage().ok()??;
+    let json = storage.get_item(SEASON_STORAGE_KEY).ok()??;
+    let saved: SavedSeason = serde_json::from_str(&json).ok()?;
+    match Season::from_saved(&saved) {
+        Ok(s) => Some(s),
+        Err(e) => {
+            info!("Could not restore saved season: {}", e);
+            None
+        }
+    }
+}
+
+fn store_season(season: &Season) {
+    let Some(storage) = window().local_storage().ok().flatten() else {
+        return;
+    };
+    match serde_json::to_string(&season.save()) {
+        Ok(json) => {
+            let _ = storage.set_item(SEASON_STORAGE_KEY, &json);
+        },
+        Err(e) => info!("Could not save season: {}", e),
+    }
+}
+
+fn load_language() -> Option<Language> {
+    let storage = window().local_storage().ok()??;
+    let code = storage.get_item(LANGUAGE_STORAGE_KEY).ok()??;
+    Language::from_code(&code)
+}
+
+fn store_language(language: Language) {
+    if let Some(storage) = window().local_storage().ok().flatten() {
+        let _ = storage.set_item(LANGUAGE_STORAGE_KEY, language.code());
+    }
+}
+
+fn load_operator_secret() -> Option<String> {
+    window().local_storage().ok()??.get_item(OPERATOR_STORAGE_KEY).ok()?
+}
+
+fn store_operator_secret(secret: &str) {
+    if let Some(storage) = window().local_storage().ok().flatten() {
+        let _ = storage.set_item(OPERATOR_STORAGE_KEY, secret);
+    }
+}
+
+// Throw server calls. Err means that the server could not be reached
+async fn fetch_remote_match() -> Result<Option<RemoteMatch>, gloo_net::Error> {
+    Request::get(API_MATCH).send().await?.json().await
+}
+
+async fn fetch_remote_status() -> Result<Option<RemoteStatus>, gloo_net::Error> {
+    Request::get(API_STATUS).send().await?.json().await
+}
+
+// None as the error means that the server could not be reached
+async fn send_remote<T: serde::Serialize>(request: RequestBuilder, body: &T) -> Result<(), Option<GameError>> {
+    let response = async { request.json(body)?.send().await }.await
+        .map_err(|_| None)?;
+    if response.ok() {
+        Ok(())
+    } else {
+        Err(response.json::<GameError>().await.ok())
+    }
+}
+
+// Opening, closing and starting matches need the secret the throw server
+// printed when it started
+async fn send_operator<T: serde::Serialize>(request: RequestBuilder, secret: &str, body: &T) -> Result<(), Option<GameError>> {
+    send_remote(request.header(OPERATOR_HEADER, secret), body).await
 }
 
+// Language of the UI, provided by App
+fn use_language() -> RwSignal<Language> {
+    use_context::<RwSignal<Language>>().expect("Language should be provided")
+}
+
+// Message for key that follows the selected language
+fn t(key: &'static str) -> impl Fn() -> &'static str + Copy {
+    let language = use_language();
+    move || tr(language.get(), key)
+}
+
+fn hand_key(hand: Rpssl) -> &'static str {
+    match hand {
+        Rpssl::Rock => "hand.rock",
+        Rpssl::Paper => "hand.paper",
+        Rpssl::Scissors => "hand.scissors",
+        Rpssl::Lizard => "hand.lizard",
+        Rpssl::Vampire => "hand.vampire",
+        Rpssl::None => "?",
+    }
+}
 
 #[server(Logging, "/logging")]
 pub async fn add_log(message: String) -> Result<(), ServerFnError> {
@@ -21,7 +263,11 @@ pub async fn add_log(message: String) -> Result<(), ServerFnError> {
 
 #[derive(Debug, Clone)]
 struct PlayerScore {
-    name: String,
+    id: u16,
+    rank: String,
+    decided_by: String,
+    tiebreaks: String,
+    player: Player,
     played: u16,
     score: u16,
 }
@@ -32,6 +278,9 @@ pub fn NameInput(
     game: WriteSignal<Game>
 ) -> impl IntoView {
     let (name, set_name) = create_signal("".to_string());
+    let (strategy, set_strategy) = create_signal(None::<StrategyKind>);
+    let (error, set_error) = create_signal(None::<GameError>);
+    let language = use_language();
     let input_element: NodeRef<Input> = create_node_ref();
     let on_submit = move |ev: SubmitEvent| {
         // Stop the page from reloading!
@@ -40,10 +289,11 @@ pub fn NameInput(
         let value = input_element.get().expect("<input> to exist").value();
         set_name.update(|n| *n = "".to_string());
         game.update(|g| {
-            match g.add_player(&value) {
-                Ok(()) => (),
-                Err(_) => info!("Player {} already exists", value),
-            }
+            let res = match strategy.get() {
+                Some(kind) => g.add_bot(&value, kind),
+                None => g.add_player(&value),
+            };
+            set_error.set(res.err());
         });
     };
     view! {
@@ -51,8 +301,111 @@ pub fn NameInput(
         <form on:submit=on_submit>
             //<label for="newp">"Uusi pelaaja "</label>
             <input type="text" id="newp" value=name prop:value=name node_ref=input_element/>
-            <input type="submit" value="Lisää"/>
+            <select id="strategy" on:change=move |ev| {
+                let new_value = event_target_value(&ev);
+                set_strategy.set(StrategyKind::iter().find(|k| k.str() == new_value));
+            }>
+                <option value="" selected=move || strategy.get().is_none()>{t("players.human")}</option>
+                {StrategyKind::iter().map(|k| view! {
+                    <option value=k.str().to_string() selected=move || strategy.get() == Some(k)>
+                        "🤖 " {k.str().to_string()}
+                    </option>
+                }).collect_view()}
+            </select>
+            <input type="submit" value=t("players.add")/>
         </form>
+        {move || error.get().map(|e| view! { <p class="error">{e.localized(language.get())}</p> })}
+        </div>
+    }
+}
+
+// Avatar and nickname in the player's colour. The full name and the
+// catchphrase show on hover
+#[component]
+fn PlayerName(
+    player: Player,
+) -> impl IntoView {
+    let avatar = match &player.profile.avatar {
+        Some(Avatar::Emoji(emoji)) => format!("{} ", emoji).into_view(),
+        Some(Avatar::Image(url)) => view! { <img class="avatar" src=url.clone()/> }.into_view(),
+        None => view! {}.into_view(),
+    };
+    let title = [Some(player.name.clone()), player.profile.catchphrase.as_ref().map(|c| format!("\"{}\"", c))]
+        .into_iter()
+        .flatten()
+        .join(" ");
+    let style = player.profile.colour.as_ref().map(|c| format!("color: {};", c));
+    view! {
+        <span class="player_tag" style=style title=title>{avatar}{player.display_name().to_string()}</span>
+    }
+}
+
+fn player_tag(game: &Game, pid: u16) -> impl IntoView {
+    game.get_player(pid).map(|player| view! { <PlayerName player=player/> })
+}
+
+// Nickname, colour, avatar and catchphrase of one player
+#[component]
+fn ProfileEditor(
+    game: ReadSignal<Game>,
+    set_game: WriteSignal<Game>,
+    pid: u16,
+) -> impl IntoView {
+    let (draft, set_draft) = create_signal(game.with_untracked(|g| g.get_player(pid).map(|p| p.profile)).unwrap_or_default());
+    let (error, set_error) = create_signal(None::<GameError>);
+    let language = use_language();
+    let emoji = move || match draft.get().avatar {
+        Some(Avatar::Emoji(e)) => e,
+        _ => "".to_string(),
+    };
+    let upload = move |ev: ev::Event| {
+        let input = event_target::<web_sys::HtmlInputElement>(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        spawn_local(async move {
+            match gloo_file::futures::read_as_data_url(&gloo_file::File::from(file)).await {
+                Ok(url) => set_draft.update(|d| d.avatar = Some(Avatar::Image(url))),
+                Err(e) => info!("Could not read picture: {}", e),
+            }
+        });
+    };
+    let save = move |_| set_game.update(|g| set_error.set(g.set_profile(pid, draft.get_untracked()).err()));
+    view! {
+        <div class="profile">
+            <p>
+                <label>{t("profile.nickname")} " "
+                    <input type="text" prop:value=move || draft.get().nickname.unwrap_or_default()
+                        on:input=move |ev| set_draft.update(|d| d.nickname = Some(event_target_value(&ev)))/>
+                </label>
+            </p>
+            <p>
+                <label>{t("profile.colour")} " "
+                    <input type="color" prop:value=move || draft.get().colour.unwrap_or("#000000".to_string())
+                        on:input=move |ev| set_draft.update(|d| d.colour = Some(event_target_value(&ev)))/>
+                </label>
+                " " <button on:click=move |_| set_draft.update(|d| d.colour = None)>{t("profile.clear")}</button>
+            </p>
+            <p>
+                <label>{t("profile.emoji")} " "
+                    <input type="text" size="4" prop:value=emoji
+                        on:input=move |ev| set_draft.update(|d| d.avatar = Some(Avatar::Emoji(event_target_value(&ev))))/>
+                </label>
+                " " <label>{t("profile.upload")} " " <input type="file" accept="image/*" on:change=upload/></label>
+                {move || match draft.get().avatar {
+                    Some(Avatar::Image(url)) => view! { " " <img class="avatar" src=url/> }.into_view(),
+                    _ => view! {}.into_view(),
+                }}
+                " " <button on:click=move |_| set_draft.update(|d| d.avatar = None)>{t("profile.clear")}</button>
+            </p>
+            <p>
+                <label>{t("profile.catchphrase")} " "
+                    <input type="text" prop:value=move || draft.get().catchphrase.unwrap_or_default()
+                        on:input=move |ev| set_draft.update(|d| d.catchphrase = Some(event_target_value(&ev)))/>
+                </label>
+            </p>
+            <button on:click=save>{t("profile.save")}</button>
+            {move || error.get().map(|e| view! { <p class="error">{e.localized(language.get())}</p> })}
         </div>
     }
 }
@@ -60,100 +413,308 @@ pub fn NameInput(
 #[component]
 pub fn PlayerList(
     game: ReadSignal<Game>,
+    set_game: WriteSignal<Game>,
 ) -> impl IntoView {
 
     //let input_element: NodeRef<Input> = create_node_ref();
-    let data = move || game.get().get_scores()
+    let page = use_page();
+    let go = use_go();
+    let selected = move || match page.get() {
+        Page::Player(pid) => Some(pid),
+        _ => None,
+    };
+    let language = use_language();
+    let tiebreaker = move |t: Tiebreaker| tr(language.get(), tiebreaker_key(t));
+    let data = move || game.with(|g| g.get_standings()
         .iter()
-        .map(|i| {
-            PlayerScore {name:i.name.clone(), played:i.played, score:i.score}
+        .map(|s| {
+            let i = g.get_player(s.pid).expect("Player should exist");
+            let tiebreaks = s.tiebreaks.iter()
+                .map(|(t, v)| format!("{}: {}", tiebreaker(*t), v))
+                .join(", ");
+            PlayerScore {
+                id:i.id,
+                rank:s.rank_str(),
+                decided_by:s.decided_by.map(tiebreaker).unwrap_or("").to_string(),
+                tiebreaks,
+                player:i.clone(),
+                played:i.played,
+                score:s.score,
+            }
         })
-        .collect::<Vec<_>>();
+        .collect::<Vec<_>>());
     view! {
         <table>
             <tr>
-                <th>Pelaaja</th>
-                <th>Ottelut</th>
-                <th>Pisteet</th>
+                <th></th>
+                <th>{t("players.name")}</th>
+                <th>{t("players.played")}</th>
+                <th>{t("players.score")}</th>
+                <th>{t("players.tiebreak")}</th>
             </tr>
             <For
                 each=move || { data() }
-                key=|p| (p.name.clone(), p.played, p.score)
-                children=|child| {
+                key=|p| (p.id, p.rank.clone(), p.decided_by.clone(), p.tiebreaks.clone(), p.player.name.clone(), p.player.profile.clone(), p.played, p.score)
+                children=move |child| {
+                    let id = child.id;
                     view! {
                         <tr>
-                            <td>{child.name}</td>
+                            <td>{child.rank}</td>
+                            <td class="player_name" on:click=move |_| {
+                                go(if selected() == Some(id) { Page::Players } else { Page::Player(id) })
+                            }>{if child.player.is_bot() { "🤖 " } else { "" }}<PlayerName player=child.player/></td>
                             <td>{child.played}</td>
                             <td>{child.score}</td>
+                            <td title=child.tiebreaks>{child.decided_by}</td>
                         </tr>
                     }
                 }
             />
 
         </table>
+        {move || selected().map(|pid| view! { <PlayerDetail game=game set_game=set_game pid=pid/> })}
+    }
+}
+
+fn tiebreaker_key(tiebreaker: Tiebreaker) -> &'static str {
+    match tiebreaker {
+        Tiebreaker::HeadToHead => "tiebreaker.head_to_head",
+        Tiebreaker::Wins => "tiebreaker.wins",
+        Tiebreaker::ResultPoints => "tiebreaker.result_points",
+        Tiebreaker::SonnebornBerger => "tiebreaker.sonneborn_berger",
+        Tiebreaker::FewestPlayed => "tiebreaker.fewest_played",
     }
 }
 
+#[component]
+pub fn PlayerDetail(
+    game: ReadSignal<Game>,
+    set_game: WriteSignal<Game>,
+    pid: u16,
+) -> impl IntoView {
+    let stats = move || game.with(|g| g.get_player_stats(pid));
+    let name = move || game.with(|g| player_tag(g, pid));
+    let catchphrase = move || game.with(|g| g.get_player(pid).and_then(|p| p.profile.catchphrase));
+    let hand = |h: Option<Rpssl>| h.map(|h| h.str().to_string()).unwrap_or("-".to_string());
+    let language = use_language();
+
+    move || match stats() {
+        Some(stats) => {
+            let streak = match &stats.current_streak {
+                Some((RpsResult::Win, n)) => tr_n(language.get(), "streak.win", *n as i64),
+                Some((RpsResult::Draw, n)) => tr_n(language.get(), "streak.draw", *n as i64),
+                Some((RpsResult::Lose, n)) => tr_n(language.get(), "streak.loss", *n as i64),
+                None => "-".to_string(),
+            };
+            view! {
+                <div class="player_detail">
+                    <h2>{name}</h2>
+                    {catchphrase().map(|c| view! { <p><i>"\"" {c} "\""</i></p> })}
+                    <ProfileEditor game=game set_game=set_game pid=pid/>
+                    <table>
+                        <tr>
+                            <th>{t("stats.hand")}</th>
+                            <th>{t("stats.thrown")}</th>
+                            <th>{t("stats.wins")}</th>
+                            <th>{t("stats.draws")}</th>
+                            <th>{t("stats.losses")}</th>
+                        </tr>
+                        {stats.hands.iter().map(|h| view! {
+                            <tr>
+                                <td class="play">{h.hand.str().to_string()}</td>
+                                <td>{h.thrown}</td>
+                                <td>{format!("{:.0} %", h.win_rate())}</td>
+                                <td>{format!("{:.0} %", h.draw_rate())}</td>
+                                <td>{format!("{:.0} %", h.loss_rate())}</td>
+                            </tr>
+                        }).collect_view()}
+                    </table>
+                    <ul>
+                        <li>{t("stats.result_points")} ": " {stats.result_points}</li>
+                        <li>{t("stats.hand_points")} ": " {stats.hand_points}</li>
+                        <li>{t("stats.streak")} ": " {streak}</li>
+                        <li>{t("stats.longest_win_streak")} ": " {stats.longest_win_streak}</li>
+                        <li>{t("stats.longest_loss_streak")} ": " {stats.longest_loss_streak}</li>
+                        <li>{t("stats.after_win")} ": " {hand(stats.after_win)}</li>
+                        <li>{t("stats.after_loss")} ": " {hand(stats.after_loss)}</li>
+                    </ul>
+                </div>
+            }.into_view()
+        }
+        None => view! {}.into_view(),
+    }
+}
+
+#[component]
+pub fn Crosstable(
+    game: ReadSignal<Game>,
+) -> impl IntoView {
+    move || {
+        let g = game.get();
+        let table = g.get_crosstable();
+        let name = |pid: u16| player_tag(&g, pid);
+        view! {
+            <table class="crosstable">
+                <tr>
+                    <th></th>
+                    {table.players.iter().map(|&p| view! { <th>{name(p)}</th> }).collect_view()}
+                </tr>
+                {table.players.iter().map(|&p1| view! {
+                    <tr>
+                        <th>{name(p1)}</th>
+                        {table.players.iter().map(|&p2| match table.get(p1, p2) {
+                            None => view! { <td class="diagonal">"X"</td> }.into_view(),
+                            Some(h2h) if h2h.played == 0 => view! { <td class="unplayed">"·"</td> }.into_view(),
+                            Some(h2h) => view! {
+                                <td>
+                                    {format!("{}-{}-{}", h2h.wins, h2h.draws, h2h.losses)}
+                                    <br/>
+                                    {format!("{}:{}", h2h.points, h2h.points_against)}
+                                </td>
+                            }.into_view(),
+                        }).collect_view()}
+                    </tr>
+                }).collect_view()}
+            </table>
+        }
+    }
+}
+
+static CHART_COLORS: [&str; 8] = [
+    "#a688fa", "#ffcc00", "#3366ff", "#ff6666", "#66cc99", "#ff9933", "#33cccc", "#cc66cc",
+];
+
+// Line chart of one value per player over the played matches.
+// With invert, smaller values are drawn higher (used for ranks)
+#[component]
+pub fn LineChart(
+    series: Vec<(String, Vec<f64>)>,
+    steps: usize,
+    invert: bool,
+) -> impl IntoView {
+    let (width, height, margin) = (600.0, 300.0, 30.0);
+    let max = series.iter().flat_map(|(_, v)| v.iter().copied()).fold(1.0, f64::max);
+    let min = if invert { 1.0 } else { 0.0 };
+    let span = (max - min).max(1.0);
+    let x = move |i: usize| margin + (width - 2.0 * margin) * i as f64 / (steps.max(2) - 1) as f64;
+    let y = move |v: f64| {
+        let t = (v - min) / span;
+        let t = if invert { t } else { 1.0 - t };
+        margin + (height - 2.0 * margin) * t
+    };
+    let viewbox = format!("0 0 {} {}", width, height);
+    view! {
+        <svg class="chart" viewBox=viewbox width="100%">
+            <line x1=margin y1=y(min) x2=margin y2=y(max) stroke="gray"/>
+            <line x1=margin y1=y(if invert { max } else { min }) x2=width - margin y2=y(if invert { max } else { min }) stroke="gray"/>
+            <text x=5 y=y(max) fill="gray" font-size="12">{max}</text>
+            <text x=5 y=y(min) fill="gray" font-size="12">{min}</text>
+            {series.into_iter().enumerate().map(|(i, (name, values))| {
+                let color = CHART_COLORS[i % CHART_COLORS.len()];
+                let points = values.iter().enumerate()
+                    .map(|(j, v)| format!("{:.1},{:.1}", x(j), y(*v)))
+                    .join(" ");
+                let last = values.last().copied().unwrap_or(min);
+                view! {
+                    <polyline points=points fill="none" stroke=color stroke-width="2"/>
+                    <text x=x(values.len().saturating_sub(1)) + 4.0 y=y(last) fill=color font-size="12">{name}</text>
+                }
+            }).collect_view()}
+        </svg>
+    }
+}
+
+#[component]
+pub fn StandingsChart(
+    game: ReadSignal<Game>,
+) -> impl IntoView {
+    move || {
+        let g = game.get();
+        let history = g.get_standings_history();
+        let name = |pid: &u16| g.get_player(*pid).map(|p| p.display_name().to_string()).unwrap_or_default();
+        let ranks = history.players.iter()
+            .map(|p| (name(p), history.ranks[p].iter().map(|r| *r as f64).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        let scores = history.players.iter()
+            .map(|p| (name(p), history.scores[p].iter().map(|s| *s as f64).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        let steps = history.steps();
+        view! {
+            <h2>{t("chart.rank")}</h2>
+            <LineChart series=ranks steps=steps invert=true/>
+            <h2>{t("chart.score")}</h2>
+            <LineChart series=scores steps=steps invert=false/>
+        }
+    }
+}
+
+// Optimal mixed strategy for the current scoring
+#[component]
+fn Equilibrium(
+    game: ReadSignal<Game>,
+) -> impl IntoView {
+    move || {
+        let eq = analysis::solve(game.with(|g| g.get_mode()));
+        view! {
+            <h2>{t("equilibrium.title")}</h2>
+            <table>
+                <tr>
+                    <th>{t("stats.hand")}</th>
+                    <th>{t("equilibrium.probability")}</th>
+                    <th>{t("equilibrium.expected")}</th>
+                </tr>
+                {eq.hands.iter().zip(eq.probabilities.iter().zip(&eq.expected)).map(|(h, (p, e))| view! {
+                    <tr>
+                        <td class="play">{h.str().to_string()}</td>
+                        <td>{format!("{:.1} %", 100.0 * p)}</td>
+                        <td>{format!("{:.2}", e)}</td>
+                    </tr>
+                }).collect_view()}
+            </table>
+            <p>{t("equilibrium.value")} ": " {format!("{:.2}", eq.value)}</p>
+        }
+    }
+}
+
+fn points_list(language: Language, points: &[(&'static str, u16)]) -> impl IntoView {
+    points.iter().map(|(key, n)| view! {
+        <li>{tr_args(language, "rule.value", &[tr(language, key), &tr_n(language, "points", *n as i64)])}</li>
+    }).collect_view()
+}
+
+fn result_points(rules: &Rules) -> Vec<(&'static str, u16)> {
+    vec![
+        ("result.win", rules.win_points),
+        ("result.draw", rules.draw_points),
+        ("result.loss", rules.loss_points),
+    ]
+}
+
+fn hand_points(rules: &Rules) -> Vec<(&'static str, u16)> {
+    rules.hand_points.iter().map(|(h, n)| (hand_key(*h), *n)).collect()
+}
+
 #[component]
 fn Scoring(
     #[prop(into)]
     game: ReadSignal<Game>,
 ) -> impl IntoView {
-    let (show_ordering, set_ordering) = create_signal(false);
+    let language = use_language();
     view! {
-        <div class="nnn" id="ordering" on:click=move |_| set_ordering.update(|value| *value = true)>
-            <Show when=move || { show_ordering.get() } fallback=|| view! { <h1>"Pisteytys"</h1>}>
-                <p class="close" on:click=move |_| set_ordering.update(|value| *value = false)>
-                    X
-                </p>
-
-                {
-                    if game.get().get_mode().str() == "RPS" {
-                    view! {
-                        <h2>"Pisteytys"</h2>
-                        <ul>
-                            <li>" Voitto: 6 pistettä"</li>
-                            <li>" Tasapeli: 3 pistettä"</li>
-                            <li>" Tappio: 0 pistettä"</li>
-                        </ul>
-                        <ul>
-                            <li>" Sakset: 3 pistettä"</li>
-                            <li>" Paperi: 2 pistettä"</li>
-                            <li>" Kivi: 1 piste"</li>
-                        </ul>
-                    }
-                } else {
-                    view! {
-                        <h2>"Pisteytys"</h2>
-                        <ul>
-                            <li>" Voitto: 10 pistettä"</li>
-                            <li>" Tasapeli: 5 pistettä"</li>
-                            <li>" Tappio: 0 pistettä"</li>
-                        </ul>
-                        <ul>
-                            <li>" Vampyyri: 5 pistettä"</li>
-                            <li>" Lisko: 4 pistettä"</li>
-                            <li>" Sakset: 3 pistettä"</li>
-                            <li>" Paperi: 2 pistettä"</li>
-                            <li>" Kivi: 1 piste"</li>
-                        </ul>
-                        <ul>
-                            <li>"Scissors cuts Paper"</li>
-                            <li>"Paper covers Rock"</li>
-                            <li>"Rock crushes Lizard"</li>
-                            <li>"Lizard poisons " <s>"Spock"</s> " Vampire"</li>
-                            <li>"Vampire smashes Scissors"</li>
-                            <li>"Scissors decapitates Lizard"</li>
-                            <li>"Lizard eats Paper"</li>
-                            <li>"Paper disproves " <s>"Spock"</s>" Vampire"</li>
-                            <li>"Vampire vaporizes Rock"</li>
-                            <li>"Rock crushes Scissors"</li>
-                        </ul>
-                    }
-                }
+        {move || {
+            let (rules, language) = (game.get().get_mode().rules(), language.get());
+            view! {
+                <h2>{tr(language, "card.scoring")}</h2>
+                <ul>{points_list(language, &result_points(&rules))}</ul>
+                <ul>{points_list(language, &hand_points(&rules))}</ul>
+                <ul>
+                    {rules.beats.iter().map(|beat| view! {
+                        <li>{lookup(language, &beat.key()).map(str::to_string).unwrap_or_else(|| beat.sentence())}</li>
+                    }).collect_view()}
+                </ul>
             }
-            </Show>
-        </div>
+        }}
+        <Equilibrium game=game/>
     }
 }
 
@@ -162,196 +723,434 @@ fn Rules(
     #[prop(into)]
     game: ReadSignal<Game>,
 ) -> impl IntoView {
-    let (show_scoring, set_scoring) = create_signal(false);
+    let language = use_language();
     view! {
-        <div class="nnn" id="rules" on:click=move |_| set_scoring.update(|value| *value = true)>
-            <Show when=move || { show_scoring.get() } fallback=|| view! { <h1>"Säännöt"</h1>}>
-                <p class="close" on:click=move |_| set_scoring.update(|value| *value = false)>
-                    X
-                </p>
-                <h2>"Säännöt"</h2>
+        <h2>{t("card.rules")}</h2>
+        {move || {
+            let (rules, language) = (game.get().get_mode().rules(), language.get());
+            let tiebreakers = game.with(|g| g.get_tiebreakers());
+            view! {
                 <ul>
-                {
-                    if game.get().get_mode().str() == "RPS" {
-                        view!{
-                            <li>"KPS - kaikki vastaan sarja"</li>
-                        }
-                    }else {
-                        view!{
-                            <li>"Kaikki vastaan kaikki sarja"</li>
-                        }
-                    }
-                }
-                    <li>"Yksi peli kerrallaan"</li>
-                    <li>"n-kertainen sarja"</li>
-                {if game.get().get_mode().str() == "RPS" {
-                    view!{
-                        <li>
-                            "Pisteitä saa tuloksesta" <ul>
-                                <li>" Voitto: 6 pistettä"</li>
-                                <li>" Tasapeli: 3 pistettä"</li>
-                                <li>" Tappio: 0 pistettä"</li>
-                            </ul>
-                        </li>
-                        <li>
-                            "ja pelatusta kädestä (riippumatta tuloksesta)" <ul>
-                                <li>" Sakset: 3 pistettä"</li>
-                                <li>" Paperi: 2 pistettä"</li>
-                                <li>" Kivi: 1 piste"</li>
-                            </ul>
-                        </li>
-                        }
+                    <li>{tr(language, if rules.game_mode == GameMode::RPS { "rules.round_robin_rps" } else { "rules.round_robin" })}</li>
+                    <li>{tr(language, "rules.one_at_a_time")}</li>
+                    <li>{tr(language, "rules.rounds")}</li>
+                    <li>
+                        {tr(language, "rules.result_points")}
+                        <ul>{points_list(language, &result_points(&rules))}</ul>
+                    </li>
+                    <li>
+                        {tr(language, "rules.hand_points")}
+                        <ul>{points_list(language, &hand_points(&rules))}</ul>
+                    </li>
+                    <li>{tr(language, "rules.winner")}</li>
+                    {if tiebreakers.is_empty() {
+                        view! { <li>{tr(language, "rules.no_tiebreakers")}</li> }.into_view()
                     } else {
-                    view! {
-                        <li>
-                            "Pisteitä saa tuloksesta" <ul>
-                                <li>" Voitto: 10 pistettä"</li>
-                                <li>" Tasapeli: 5 pistettä"</li>
-                                <li>" Tappio: 0 pistettä"</li>
-                            </ul>
-                        </li>
-                        <li>
-                            "ja pelatusta kädestä (riippumatta tuloksesta)" <ul>
-                                <li>" Vampyyri: 5 pistettä"</li>
-                                <li>" Lisko: 4 pistettä"</li>
-                                <li>" Sakset: 3 pistettä"</li>
-                                <li>" Paperi: 2 pistettä"</li>
-                                <li>" Kivi: 1 piste"</li>
-                            </ul>
-                        </li>
-                        }
-                    }
-            }
-                    <li>Eniten pisteitä kerännyt on voittaja</li>
-                    <li>Tasapisteissä voittajan ratkaisee paras viidestä - kaksinkamppailu</li>
+                        view! {
+                            <li>
+                                {tr(language, "rules.tiebreakers")}
+                                <ol>{tiebreakers.into_iter().map(|t| view! { <li>{tr(language, tiebreaker_key(t))}</li> }).collect_view()}</ol>
+                            </li>
+                        }.into_view()
+                    }}
                     //<li>"Psyykkinen sodankäynti on sallittua"</li>
                 </ul>
-            </Show>
-        </div>
+            }
+        }}
     }
 }
 
 
+// Time to undo a result before it is saved
+static UNDO_DELAY: Duration = Duration::from_millis(2500);
+
 #[component]
 pub fn CurrentMatch(
     #[prop(into)]
     game: ReadSignal<Game>,
     set_game: WriteSignal<Game>,
 ) -> impl IntoView {
-    //let (value, set_value) = create_signal("B".to_string());
-    //
+    let (hand1, set_hand1) = create_signal(None::<Rpssl>);
+    let (hand2, set_hand2) = create_signal(None::<Rpssl>);
+    // Result waiting for the undo window to pass
+    let (pending, set_pending) = create_signal(None::<TimeoutHandle>);
+    let language = use_language();
+    // Players send their hands from their own devices through the throw server
+    let (remote, set_remote) = create_signal(false);
+    let (status, set_status) = create_signal(None::<RemoteStatus>);
+    let remote_match = create_memo(move |_| {
+        if !remote.get() {
+            return None;
+        }
+        game.with(|g| g.get_next_game()
+            .and_then(|m| RemoteMatch::new(g, (m.player1, m.player2, m.round)).ok()))
+    });
+    // Secret of the throw server, asked once and kept in the browser
+    let (secret, set_secret) = create_signal(load_operator_secret().unwrap_or_default());
+    let (remote_error, set_remote_error) = create_signal(None::<GameError>);
+    let report = move |result: Result<(), Option<GameError>>| match result {
+        Ok(()) => set_remote_error.set(None),
+        Err(Some(e)) => set_remote_error.set(Some(e)),
+        Err(None) => info!("Could not reach the throw server"),
+    };
+    // Each player's link has a token of its own, so that nobody else can
+    // throw for them. The links stay the same for the whole session
+    let tokens = store_value(BTreeMap::<u16, String>::new());
+    let token = move |pid: u16| {
+        tokens.update_value(|t| {
+            t.entry(pid).or_insert_with(|| random_nonce(&mut rand::thread_rng()));
+        });
+        tokens.with_value(|t| t[&pid].clone())
+    };
+    let open_remote = move |remote: RemoteMatch| {
+        let side = |pid: u16, human: bool| if human { token(pid) } else { String::new() };
+        let tokens = (side(remote.game_id.0, remote.human1), side(remote.game_id.1, remote.human2));
+        let secret = secret.get_untracked();
+        spawn_local(async move {
+            report(send_operator(Request::put(API_MATCH), &secret, &OpenMatch {remote, tokens}).await);
+        });
+    };
+    let close_remote = move |game_id: (u16, u16, u16)| {
+        let secret = secret.get_untracked();
+        spawn_local(async move {
+            report(send_operator(Request::delete(API_MATCH), &secret, &game_id).await);
+        });
+    };
+    create_effect(move |_| {
+        set_status.set(None);
+        secret.track();
+        if let Some(m) = remote_match.get() {
+            open_remote(m);
+        }
+    });
+    let countdown = move || game.with(|g| g.get_countdown());
+    // Local time when hands are locked, and the timer that locks them
+    let (deadline, set_deadline) = create_signal(None::<f64>);
+    let (lock_timer, set_lock_timer) = create_signal(None::<TimeoutHandle>);
 
-    let (value, set_value) = create_signal("?".to_string());
-    let (value2, set_value2) = create_signal("?".to_string());
-        
-    let on_submit = move |ev: SubmitEvent| {
-        // Stop the page from reloading!
-        ev.prevent_default();
-        let play1 = value.get();
-        let play2 = value2.get();
+    let reset = move || {
+        set_pending.set(None);
+        set_hand1.set(None);
+        set_hand2.set(None);
+        set_deadline.set(None);
+        if let Some(handle) = lock_timer.get_untracked() {
+            handle.clear();
+        }
+        set_lock_timer.set(None);
+    };
 
-        if (play1 == "?") | (play2 == "?") {
-            info!("One option was ?");
-            return;
+    // Hands still missing at the end of the countdown are late
+    let submit_timed = move |game_id: (u16, u16, u16)| {
+        let (play1, play2) = (hand1.get_untracked(), hand2.get_untracked());
+        reset();
+        set_game.update(|g| {
+            if let Err(e) = g.add_timed_result(game_id, play1, play2) {
+                info!("Could not add result: {}", e);
+            }
+        });
+    };
+
+    let submit = move |game_id: (u16, u16, u16)| {
+        let (player1_id, player2_id, _round) = game_id;
+        let bot1 = game.with_untracked(|g| g.get_player(player1_id).unwrap().is_bot());
+        let bot2 = game.with_untracked(|g| g.get_player(player2_id).unwrap().is_bot());
+        // Bots throw for themselves, so only the human hands are needed
+        let play1 = if bot1 { Rpssl::None } else { hand1.get_untracked().unwrap_or(Rpssl::None) };
+        let play2 = if bot2 { Rpssl::None } else { hand2.get_untracked().unwrap_or(Rpssl::None) };
+        // Hands thrown remotely are added with their commitments for later audit
+        let commitments = status.get_untracked()
+            .filter(|s| remote.get_untracked() & (s.game_id == game_id))
+            .and_then(|s| s.commitments);
+        reset();
+        if remote.get_untracked() {
+            close_remote(game_id);
         }
-        set_value.update(|v| *v = "?".to_string());
-        set_value2.update(|v| *v = "?".to_string());
-        let m = game.get().get_next_game().unwrap().clone();
-        let player1_id = m.player1;
-        let player2_id = m.player2;
-        let round = m.round;
-        let play1 = Rpssl::new(&play1[..]);
-        let play2 = Rpssl::new(&play2[..]);
-        let message = format!("Add result for {} {} v. {} {}", player1_id, play1, player2_id, play2).to_string();
-        debug!("{}", message);
+        debug!("Add result for {} {} v. {} {}", player1_id, play1, player2_id, play2);
         set_game.update(|g| {
-            //spawn_local(async {
-            //    add_log("So much to do!".to_string()).await;
-            //});
-            g.add_result((player1_id,player2_id, round), play1, play2)}
-        );
+            if let Some((commitment1, commitment2)) = commitments {
+                if let Err(e) = g.add_committed_result(game_id, commitment1, commitment2) {
+                    info!("Could not add result: {}", e);
+                }
+            } else if bot1 | bot2 {
+                g.add_result_with_bots(game_id, play1, play2)
+            } else {
+                g.add_result(game_id, play1, play2)
+            }
+        });
+    };
+
+    let undo = move || {
+        if let Some(handle) = pending.get_untracked() {
+            handle.clear();
+        }
+        reset();
+        // Players throw again
+        if let Some(m) = remote_match.get_untracked() {
+            set_status.set(None);
+            open_remote(m);
+        }
+    };
+
+    // Next match and whether each side still has to choose a hand
+    let next = move || game.with_untracked(|g| {
+        g.get_next_game().map(|m| (
+            (m.player1, m.player2, m.round),
+            !g.get_player(m.player1).unwrap().is_bot(),
+            !g.get_player(m.player2).unwrap().is_bot(),
+        ))
+    });
+
+    // With a countdown the hands are locked when it ends
+    let start_countdown = move || {
+        let (Some(c), Some((game_id, _, _))) = (countdown(), next()) else {
+            return;
+        };
+        if remote.get_untracked() {
+            let secret = secret.get_untracked();
+            spawn_local(async move {
+                report(send_operator(Request::post(API_START), &secret, &game_id).await);
+            });
+            return;
+        }
+        set_deadline.set(Some(js_sys::Date::now() + c.duration_ms() as f64));
+        let duration = Duration::from_millis(c.duration_ms());
+        if let Ok(handle) = set_timeout_with_handle(move || submit_timed(game_id), duration) {
+            set_lock_timer.set(Some(handle));
+        }
+    };
+    let timed = move || countdown().is_some() & !remote.get_untracked();
+
+    // Saves the result after the undo window once both humans have chosen
+    let choose = move |side: u8, hand: Rpssl| {
+        if pending.get_untracked().is_some() {
+            return;
+        }
+        let Some((game_id, human1, human2)) = next() else {
+            return;
+        };
+        if side == 1 {
+            set_hand1.set(Some(hand));
+        } else {
+            set_hand2.set(Some(hand));
+        }
+        let done1 = !human1 | hand1.get_untracked().is_some();
+        let done2 = !human2 | hand2.get_untracked().is_some();
+        if done1 & done2 & !timed() {
+            match set_timeout_with_handle(move || submit(game_id), UNDO_DELAY) {
+                Ok(handle) => set_pending.set(Some(handle)),
+                Err(_) => submit(game_id),
+            }
+        }
+    };
+
+    // Hands from the throw server are chosen like the operator's clicks,
+    // so the undo window works the same way
+    let poll = move || {
+        if !remote.get_untracked() {
+            return;
+        }
+        spawn_local(async move {
+            let current = fetch_remote_status().await.ok().flatten();
+            set_status.set(current.clone());
+            let (Some(current), Some((game_id, human1, human2))) = (current, next()) else {
+                return;
+            };
+            // Count down from the time left on the server
+            match current.countdown_left.filter(|_| current.game_id == game_id) {
+                Some(left) if deadline.get_untracked().is_none() => set_deadline.set(Some(js_sys::Date::now() + left as f64)),
+                Some(_) => (),
+                None => set_deadline.set(None),
+            }
+            let Some((play1, play2)) = current.hands().filter(|_| current.game_id == game_id) else {
+                return;
+            };
+            if human1 {
+                choose(1, play1);
+            }
+            if human2 {
+                choose(2, play2);
+            }
+        });
+    };
+    if let Ok(handle) = set_interval_with_handle(poll, POLL_INTERVAL) {
+        on_cleanup(move || handle.clear());
+    }
+
+    // Hand shortcuts go to player 1 first, then to player 2
+    let listener = window_event_listener(ev::keydown, move |ev| {
+        let tag = event_target::<web_sys::Element>(&ev).tag_name();
+        if ["INPUT", "SELECT", "TEXTAREA"].contains(&tag.as_str()) | ev.ctrl_key() | ev.meta_key() | ev.alt_key() {
+            return;
+        }
+        let key = ev.key();
+        if (key == "Escape") | (key == "Backspace") {
+            undo();
+            return;
+        }
+        let mut chars = key.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return;
+        };
+        let Some(hand) = Rpssl::from_shortcut(c) else {
+            return;
+        };
+        if remote.get_untracked() {
+            return;
+        }
+        if !game.with_untracked(|g| g.get_mode().hands().contains(&hand)) {
+            return;
+        }
+        let Some((_game_id, human1, human2)) = next() else {
+            return;
+        };
+        if human1 & hand1.get_untracked().is_none() {
+            choose(1, hand);
+        } else if human2 & hand2.get_untracked().is_none() {
+            choose(2, hand);
+        }
+    });
+    on_cleanup(move || listener.remove());
+
+    let hand_buttons = move |side: u8, hand: ReadSignal<Option<Rpssl>>| {
+        game.with(|g| g.get_mode().hands()).into_iter().map(|h| view! {
+            <button
+                class="hand"
+                class:selected=move || hand.get() == Some(h)
+                title=h.shortcut().to_string()
+                on:click=move |_| choose(side, h)>
+                {h.str().to_string()}
+            </button>
+        }).collect_view()
     };
 
-    let mode = move || game.with(|g| g.get_mode());
+    // Whether a player has thrown on their own device
+    let remote_state = move |side: u8| move || match status.get() {
+        Some(s) => {
+            let (committed, revealed) = if side == 1 { (s.committed1, s.revealed1) } else { (s.committed2, s.revealed2) };
+            match (committed, revealed) {
+                (_, true) => format!("✅ {}", tr(language.get(), "remote.revealed")),
+                (true, false) => format!("🔒 {}", tr(language.get(), "remote.locked")),
+                _ => format!("⏳ {}", tr(language.get(), "remote.waiting")),
+            }
+        },
+        None => tr(language.get(), "remote.offline").to_string(),
+    };
+    let hand_entry = move |side: u8, hand: ReadSignal<Option<Rpssl>>| {
+        if remote.get() {
+            view! { <span>{remote_state(side)}</span> }.into_view()
+        } else {
+            hand_buttons(side, hand)
+        }
+    };
+    let origin = window().location().origin().unwrap_or_default();
 
     move || match game.get().get_next_game() {
         Some(m) => {
-            let p1 = m.player1;
-            let p2 = m.player2;
-            let player1_name = game.get().get_player_name(p1).unwrap();
-            let player2_name = game.get().get_player_name(p2).unwrap();
+            let game_id = (m.player1, m.player2, m.round);
+            let origin = origin.clone();
+            let player1_name = game.with(|g| player_tag(g, m.player1));
+            let player2_name = game.with(|g| player_tag(g, m.player2));
+            let bot1 = game.with(|g| g.get_player(m.player1).unwrap().is_bot());
+            let bot2 = game.with(|g| g.get_player(m.player2).unwrap().is_bot());
             let n_games = game.get().get_left_n();
+            let on_deck = game.with(|g| g.get_on_deck().map(|d| view! {
+                {player_tag(g, d.player1)} " - " {player_tag(g, d.player2)}
+            }));
 
             view! {
-                <form on:submit=on_submit>
-                    // <p>"Round" {round}</p>
-                    <p id="seuraavana">
-                        {player1_name} "   " // <span class="play_select">"🪨"</span>
-                        // <span class="play_select">"📜"</span>
-                        // <span class="play_select">"✂️"</span>
-                        <select id="player1select" on:change=move |ev| {
-                            let new_value = event_target_value(&ev);
-                            set_value.set(new_value);
-                        }>
-                            <SelectOption value is="?"/>
-                            <SelectOption value is=Rpssl::Rock.str()/>
-                            <SelectOption value is=Rpssl::Paper.str()/>
-                            <SelectOption value is=Rpssl::Scissors.str()/>
-                            { if mode() == GameMode::RPSSL {
-                                view! {
-                                    <SelectOption value is=Rpssl::Vampire.str()/>
-                                    <SelectOption value is=Rpssl::Lizard.str()/>
-                                }.into_view()
-                            } else {
-                                view! {}.into_view()
-                            }
-                            }
-                        </select>
-                        " Vs. " {player2_name} "   "
-                        <select id="player2select" on:change=move |ev| {
-                            let new_value = event_target_value(&ev);
-                            set_value2.set(new_value);
-                        }>
-                            <SelectOption value=value2 is="?"/>
-                            <SelectOption value=value2 is=Rpssl::Rock.str()/>
-                            <SelectOption value=value2 is=Rpssl::Paper.str()/>
-                            <SelectOption value=value2 is=Rpssl::Scissors.str()/>
-                            { if mode() == GameMode::RPSSL {
-                                view! {
-                                    <SelectOption value=value2 is=Rpssl::Vampire.str()/>
-                                    <SelectOption value=value2 is=Rpssl::Lizard.str()/>
-                                }.into_view()
-                            } else {
-                                view! {}.into_view()
-                            }
-                            }
-                        </select>
-                        <input type="submit" value="Lisää"/>
+                {move || countdown().map(|c| match deadline.get() {
+                    Some(d) => view! { <CountdownWords countdown=c deadline=d/> }.into_view(),
+                    None => view! {
+                        <p><button on:click=move |_| start_countdown()>{t("countdown.start")}</button></p>
+                    }.into_view(),
+                })}
+                <div id="seuraavana">
+                    <p>
+                        {player1_name} " "
+                        {if bot1 {
+                            view! { <span class="play">"🤖"</span> }.into_view()
+                        } else {
+                            hand_entry(1, hand1)
+                        }}
                     </p>
-                </form>
-                    {if n_games > 1 {
+                    <p>{t("vs")}</p>
+                    <p>
+                        {player2_name} " "
+                        {if bot2 {
+                            view! { <span class="play">"🤖"</span> }.into_view()
+                        } else {
+                            hand_entry(2, hand2)
+                        }}
+                    </p>
+                    {move || if pending.get().is_some() {
+                        view! {
+                            <p>{t("play.saving")} " " <button on:click=move |_| undo()>{t("play.undo")}</button></p>
+                        }.into_view()
+                    } else if bot1 & bot2 {
                         view! {
-                            <p>{n_games} " peliä jäljellä"</p>
-                        }
+                            <p><button on:click=move |_| submit(game_id)>{t("play.play")}</button></p>
+                        }.into_view()
                     } else {
-                            view! {
-                                <p>"1 peli jäljellä"</p>
-                            }
-                        }
+                        view! {}.into_view()
+                    }}
+                </div>
+                {on_deck.map(|d| view! { <p>{t("play.on_deck")} ": " {d}</p> })}
+                <p>{move || tr_n(language.get(), "games_left", n_games as i64)}</p>
+                <p>
+                    <label>
+                        <input type="checkbox" prop:checked=remote on:change=move |_| set_remote.update(|r| *r = !*r)/>
+                        " " {t("remote.enable")}
+                    </label>
+                </p>
+                {move || remote.get().then(|| {
+                    let links = [(game_id.0, bot1), (game_id.1, bot2)].into_iter()
+                        .filter(|(_pid, bot)| !bot)
+                        .map(|(pid, _bot)| {
+                            let url = format!("{}/throw/{}/{}", origin, pid, token(pid));
+                            let name = game.with(|g| g.get_player(pid).map(|p| p.display_name().to_string())).unwrap_or_default();
+                            view! { <li>{name} ": " <a href=url.clone() target="_blank">{url.clone()}</a></li> }
+                        })
+                        .collect_view();
+                    view! {
+                        <p>
+                            <label>
+                                {t("remote.secret")} ": "
+                                <input type="password" prop:value=secret on:change=move |ev| {
+                                    let value = event_target_value(&ev);
+                                    store_operator_secret(&value);
+                                    set_secret.set(value);
+                                }/>
+                            </label>
+                        </p>
+                        {move || remote_error.get().map(|e| view! { <p class="error">{e.localized(language.get())}</p> })}
+                        <p>{t("remote.links")} ":"</p><ul>{links}</ul>
                     }
+                })}
             }.into_view()
         }
         _ => view! {<p>"-"</p>}.into_view(),
     }
 }
 
+// "Kivi! Paperi! Sakset!", one word per beat until the hands are locked
 #[component]
-pub fn SelectOption(is: &'static str, value: ReadSignal<String>) -> impl IntoView {
-    view! {
-        <option value=is selected=move || value.get() == is>
-            {is}
-        </option>
+fn CountdownWords(
+    countdown: Countdown,
+    deadline: f64,
+) -> impl IntoView {
+    let (clock, set_clock) = create_signal(js_sys::Date::now());
+    if let Ok(handle) = set_interval_with_handle(move || set_clock.set(js_sys::Date::now()), TICK) {
+        on_cleanup(move || handle.clear());
     }
+    let language = use_language();
+    let word = move || {
+        let left = ((deadline - clock.get()).max(0.0) as u64).min(countdown.duration_ms());
+        match countdown.beat_at(countdown.duration_ms() - left) {
+            Some(beat) => tr(language.get(), COUNTDOWN_WORDS[beat]).to_string(),
+            None => format!("🔒 {}", tr(language.get(), "countdown.locked")),
+        }
+    };
+    view! { <p class="countdown">{word}</p> }
 }
 
 #[allow(dead_code)]
@@ -359,14 +1158,19 @@ pub fn SelectOption(is: &'static str, value: ReadSignal<String>) -> impl IntoVie
 struct GameScore {
     name1: String,
     name2: String,
+    player1: Player,
+    player2: Player,
     play1: String,
     play2: String,
+    hand1: Rpssl,
+    hand2: Rpssl,
     id1: u16,
     id2: u16,
     score1: u16,
     score2: u16,
     prior: i64,
     round: u16,
+    commentary: String,
 }
 
 #[component]
@@ -375,71 +1179,138 @@ game: ReadSignal<Game>,
 set_game: WriteSignal<Game>,
 ) -> impl IntoView {
 
-    let data = move || game.get().get_played_games()
-        .iter()
-        .rev()
-        .map(|(m,p)| {
-
-            let id1 = m.player1;
-            let id2 = m.player2;
-            let player1 = game.get().get_player_name(id1).unwrap();
-            let player2 = game.get().get_player_name(id2).unwrap();
-            let (score1, score2) = m.get_score(game.get().get_mode());
-            let play1 = m.play1.str().to_string();
-            let play2 = m.play2.str().to_string();
-
-            GameScore {
-                name1: player1.clone(),
-                name2: player2.clone(),
-                play1,
-                play2,
-                id1,
-                id2,
-                score1,
-                score2,
-                prior: *p,
-                round: m.round,
-            }
-        }).with_position()
-        .collect::<Vec<_>>();
+    let language = use_language();
+    let data = move || {
+        let mut commentary = game.with(|g| g.get_commentary(language.get()));
+        game.get().get_played_games()
+            .iter()
+            .rev()
+            .map(|(m,p)| {
+
+                let id1 = m.player1;
+                let id2 = m.player2;
+                let player1 = game.get().get_player(id1).unwrap();
+                let player2 = game.get().get_player(id2).unwrap();
+                let (score1, score2) = m.get_score(game.get().get_mode());
+                let play1 = m.play1.str().to_string();
+                let play2 = m.play2.str().to_string();
+
+                GameScore {
+                    name1: player1.name.clone(),
+                    name2: player2.name.clone(),
+                    player1,
+                    player2,
+                    play1,
+                    play2,
+                    hand1: m.play1,
+                    hand2: m.play2,
+                    id1,
+                    id2,
+                    score1,
+                    score2,
+                    prior: *p,
+                    round: m.round,
+                    commentary: commentary.remove(&(id1, id2, m.round)).unwrap_or_default(),
+                }
+            }).with_position()
+            .collect::<Vec<_>>()
+    };
+    let latest = move || game.with(|g| g.get_latest_commentary(language.get()));
 
     let quote = move || {
         let (quote, author) = game.get().get_quote();
         view! {<p>"\"" {quote} "\""</p><p>" - "{author}</p>}
     };
 
+    let page = use_page();
+    let go = use_go();
+    // Match being corrected, with the hands chosen so far
+    let (editing, set_editing) = create_signal(None::<((u16, u16, u16), Rpssl, Rpssl)>);
+    let hand_picker = move |side: u8| {
+        game.with(|g| g.get_mode().hands()).into_iter().map(|h| {
+            let selected = move || editing.with(|e| match e {
+                Some((_, h1, _)) if side == 1 => *h1 == h,
+                Some((_, _, h2)) => *h2 == h,
+                None => false,
+            });
+            view! {
+                <button
+                    class="hand small"
+                    class:selected=selected
+                    on:click=move |_| set_editing.update(|e| match e {
+                        Some((_, h1, _)) if side == 1 => *h1 = h,
+                        Some((_, _, h2)) => *h2 = h,
+                        None => (),
+                    })>
+                    {h.str().to_string()}
+                </button>
+            }
+        }).collect_view()
+    };
+    let save_edit = move || {
+        let Some((game_id, play1, play2)) = editing.get_untracked() else {
+            return;
+        };
+        set_editing.set(None);
+        set_game.update(|g| {
+            if let Err(e) = g.edit_result(game_id, play1, play2) {
+                info!("Could not edit result: {}", e);
+            }
+        });
+    };
+    let remove = move |game_id: (u16, u16, u16)| set_game.update(|g| {
+        if let Err(e) = g.remove_result(game_id) {
+            info!("Could not remove result: {}", e);
+        }
+    });
+
     view! {
-        <h2>Seuraavana:</h2>
-        <p>
+        <h2>{t("card.games")}</h2>
+        {move || match page.get() {
+            Page::Match(game_id) => view! { <MatchDetail game=game game_id=game_id/> }.into_view(),
+            _ => view! {}.into_view(),
+        }}
+        <div>
             <CurrentMatch game=game set_game=set_game/>
-        </p>
+        </div>
+        {move || latest().map(|c| view! { <p class="result_banner">{c}</p> })}
         <hr/>
         <div>{quote}</div>
         <hr/>
 
         <table>
-            <tr>
-                <td class="trashcan"
-            on:click = move |_| {set_game.update(|g| g.remove_latest())}
-            >"🗑️"</td>
-            </tr>
             <For
                 each=move || { data() }
-                key=|(_pos, p)| (p.name1.clone(), p.name2.clone(), p.round)
-                children=|(_pos, child)| {
+                key=|(_pos, p)| (p.name1.clone(), p.name2.clone(), p.player1.profile.clone(), p.player2.profile.clone(), p.round, p.hand1, p.hand2, p.commentary.clone())
+                children=move |(_pos, child)| {
+                    let game_id = (child.id1, child.id2, child.round);
+                    let (hand1, hand2) = (child.hand1, child.hand2);
+                    let is_editing = move || editing.with(|e| e.map(|(id, _, _)| id) == Some(game_id));
+                    let (play1, play2) = (child.play1.clone(), child.play2.clone());
                     view! {
                         <tr>
                             //<td>{child.round}</td>
                             //<td>{child.prior}</td>
-                            <td class="play">{child.play1}</td>
-                            <td>{child.score1} "p"</td>
-                            <td style="text-align:right;">{child.name1} </td>
-                            <td>"Vs."</td>
-                            <td>{child.name2}</td>
-                            <td class="play">{child.play2}</td>
-                            <td>{child.score2} "p"</td>
-                            
+                            <td class="play">{move || if is_editing() { hand_picker(1) } else { play1.clone().into_view() }}</td>
+                            <td>{move || tr_n(language.get(), "points.short", child.score1 as i64)}</td>
+                            <td class="player_name" style="text-align:right;" on:click=move |_| go(Page::Match(game_id))><PlayerName player=child.player1/> </td>
+                            <td>{t("vs")}</td>
+                            <td class="player_name" on:click=move |_| go(Page::Match(game_id))><PlayerName player=child.player2/></td>
+                            <td class="play">{move || if is_editing() { hand_picker(2) } else { play2.clone().into_view() }}</td>
+                            <td>{move || tr_n(language.get(), "points.short", child.score2 as i64)}</td>
+                            {move || if is_editing() {
+                                view! {
+                                    <td class="edit" on:click=move |_| save_edit()>"✔️"</td>
+                                    <td class="edit" on:click=move |_| set_editing.set(None)>"✖️"</td>
+                                }.into_view()
+                            } else {
+                                view! {
+                                    <td class="edit" on:click=move |_| set_editing.set(Some((game_id, hand1, hand2)))>"✏️"</td>
+                                    <td class="trashcan" on:click=move |_| remove(game_id)>"🗑️"</td>
+                                }.into_view()
+                            }}
                         </tr>
+                        <tr class="commentary"><td colspan="9">{child.commentary}</td></tr>
                     }
                 }
             />
@@ -449,66 +1320,557 @@ set_game: WriteSignal<Game>,
     }
 }
 
+// Single match, opened from the match list or a shared link
+#[component]
+fn MatchDetail(
+    game: ReadSignal<Game>,
+    game_id: (u16, u16, u16),
+) -> impl IntoView {
+    let go = use_go();
+    let language = use_language();
+    let name = move |pid: u16| game.with(|g| player_tag(g, pid));
+    move || {
+        let Some((m, mode)) = game.with(|g| g.get_match(game_id).cloned().map(|m| (m, g.get_mode()))) else {
+            return view! { <p class="error">{GameError::NoSuchGame.localized(language.get())}</p> }.into_view();
+        };
+        let (score1, score2) = m.get_score(mode);
+        let round = m.round.to_string();
+        view! {
+            <div class="match_detail">
+                <h3>{name(m.player1)} " " {t("vs")} " " {name(m.player2)}</h3>
+                <p>{move || tr_args(language.get(), "schedule.round", &[&round])}</p>
+                {match m.result {
+                    Some(_) => view! {
+                        <p>
+                            <span class="play">{m.play1.str().to_string()}</span> " "
+                            {score1} " - " {score2} " "
+                            <span class="play">{m.play2.str().to_string()}</span>
+                        </p>
+                    }.into_view(),
+                    None => view! { <p>{t("match.unplayed")}</p> }.into_view(),
+                }}
+                {[(m.player1, m.commitments.0), (m.player2, m.commitments.1)].into_iter()
+                    .filter_map(|(pid, c)| c.map(|c| (pid, c)))
+                    .map(|(pid, c)| view! {
+                        <p title=c.nonce.clone()>
+                            {name(pid)} ": " {t("match.commitment")} " "
+                            <code>{c.hash[..12].to_string()}</code> " "
+                            {if c.verify() { "✔️" } else { "❌" }}
+                        </p>
+                    })
+                    .collect_view()}
+                <button on:click=move |_| go(Page::Play)>{t("match.back")}</button>
+            </div>
+            <hr/>
+        }.into_view()
+    }
+}
+
+#[component]
+pub fn Schedule(
+    game: ReadSignal<Game>,
+    set_game: WriteSignal<Game>,
+) -> impl IntoView {
+    let pin = move |game_id: (u16, u16, u16)| set_game.update(|g| {
+        if let Err(e) = g.pin_match(game_id) {
+            info!("Could not pin match: {}", e);
+        }
+    });
+    let push_back = move |game_id: (u16, u16, u16)| set_game.update(|g| {
+        if let Err(e) = g.push_back(game_id) {
+            info!("Could not push match back: {}", e);
+        }
+    });
+    let toggle_away = move |pid: u16| set_game.update(|g| {
+        let away = g.get_player(pid).is_some_and(|p| p.away);
+        let _ = g.set_away(pid, !away);
+    });
+    let language = use_language();
+    move || {
+        let language = language.get();
+        let g = game.get();
+        let next = g.get_next_game().map(|m| (m.player1, m.player2, m.round));
+        let on_deck = g.get_on_deck().map(|m| (m.player1, m.player2, m.round));
+        let name = |pid: u16| {
+            let away = g.get_player(pid).is_some_and(|p| p.away);
+            view! { {player_tag(&g, pid)} {if away { " 💤" } else { "" }} }
+        };
+        view! {
+            <h2>{tr(language, "card.schedule")}</h2>
+            <table>
+                {g.get_schedule().into_iter().enumerate().map(|(i, m)| {
+                    let game_id = (m.player1, m.player2, m.round);
+                    let status = if Some(game_id) == next {
+                        tr(language, "schedule.now")
+                    } else if Some(game_id) == on_deck {
+                        tr(language, "schedule.on_deck")
+                    } else if g.is_pinned(game_id) {
+                        "📌"
+                    } else if g.is_deferred(game_id) {
+                        "⏬"
+                    } else {
+                        ""
+                    };
+                    view! {
+                        <tr>
+                            <td>{i + 1}"."</td>
+                            <td>{status}</td>
+                            <td style="text-align:right;">{name(m.player1)}</td>
+                            <td>{tr(language, "vs")}</td>
+                            <td>{name(m.player2)}</td>
+                            <td>{tr_args(language, "schedule.round", &[&m.round.to_string()])}</td>
+                            <td class="edit" title=tr(language, "schedule.pin") on:click=move |_| pin(game_id)>"📌"</td>
+                            <td class="edit" title=tr(language, "schedule.push_back") on:click=move |_| push_back(game_id)>"⏬"</td>
+                        </tr>
+                    }
+                }).collect_view()}
+            </table>
+            <h3>{tr(language, "schedule.away")}</h3>
+            {g.player_list.values().map(|p| {
+                let pid = p.id;
+                view! {
+                    <label>
+                        <input type="checkbox" prop:checked=p.away on:change=move |_| toggle_away(pid)/>
+                        <PlayerName player=p.clone()/>
+                    </label>
+                    " "
+                }
+            }).collect_view()}
+        }
+    }
+}
+
+#[component]
+pub fn SeasonPanel(
+    game: ReadSignal<Game>,
+    set_game: WriteSignal<Game>,
+    season: ReadSignal<Season>,
+    set_season: WriteSignal<Season>,
+) -> impl IntoView {
+    // Roster players left out of the next tournament
+    let (away, set_away) = create_signal(Vec::<u16>::new());
+    let in_season = move || season.with(|s| s.find_tournament(game.with(|g| g.get_seed())).is_some());
+    let add_current = move |_| set_season.update(|s| {
+        s.add_tournament(game.get());
+    });
+    let new_tournament = move |_| {
+        let mut s = season.get();
+        let present = s.roster.keys().copied().filter(|id| !away.get().contains(id)).collect::<Vec<_>>();
+        match s.new_tournament(&present) {
+            Ok(i) => {
+                set_game.set(s.tournaments[i].game.clone());
+                set_season.set(s);
+            },
+            Err(e) => info!("Could not start tournament: {}", e),
+        }
+    };
+    let toggle_away = move |id: u16| set_away.update(|a| {
+        match a.iter().position(|p| *p == id) {
+            Some(i) => { a.remove(i); },
+            None => a.push(id),
+        }
+    });
+    move || {
+        let s = season.get();
+        let name = |id: u16| s.roster.get(&id).map(|p| p.name.clone()).unwrap_or_default();
+        view! {
+            <h2>{s.name.clone()}</h2>
+            <table>
+                <tr>
+                    <th></th>
+                    <th>{t("players.name")}</th>
+                    <th>{t("season.points")}</th>
+                    <th>{t("season.wins")}</th>
+                    <th>{t("season.best")}</th>
+                    <th>{t("season.attended")}</th>
+                    {(1..=s.tournaments.len()).map(|i| view! { <th>{i}</th> }).collect_view()}
+                    <th>{t("season.present")}</th>
+                </tr>
+                {s.get_standings().into_iter().enumerate().map(|(i, standing)| {
+                    let id = standing.id;
+                    view! {
+                        <tr>
+                            <td>{i + 1}"."</td>
+                            <td>{name(id)}</td>
+                            <td>{standing.points}</td>
+                            <td>{standing.tournament_wins}</td>
+                            <td>{standing.best.map(|b| format!("{}.", b)).unwrap_or("-".to_string())}</td>
+                            <td>{standing.attended}</td>
+                            {s.get_attendance(id).into_iter().map(|a| view! {
+                                <td>{if a { "✓" } else { "·" }}</td>
+                            }).collect_view()}
+                            <td>
+                                <input type="checkbox"
+                                    prop:checked=move || !away.get().contains(&id)
+                                    on:change=move |_| toggle_away(id)/>
+                            </td>
+                        </tr>
+                    }
+                }).collect_view()}
+            </table>
+            <p>{t("season.placing_points")} ": " {s.points.iter().join(", ")}</p>
+            <p>
+                <Show when=move || !in_season() fallback=|| view! {}>
+                    <button on:click=add_current>{t("season.add_current")}</button>
+                </Show>
+                <button on:click=new_tournament>{t("season.new_tournament")}</button>
+            </p>
+        }
+    }
+}
+
+// Downloads of printable sheets as standalone HTML, and entry of the
+// results written on them
+#[component]
+pub fn Printouts(
+    game: ReadSignal<Game>,
+    set_game: WriteSignal<Game>,
+) -> impl IntoView {
+    let language = use_language();
+    let (text, set_text) = create_signal(String::new());
+    let (message, set_message) = create_signal(None::<Result<usize, (usize, GameError)>>);
+    let data_url = |html: String| format!("data:text/html;charset=utf-8,{}", js_sys::encode_uri_component(&html));
+    let crosstable = move || data_url(game.with(|g| g.crosstable_html(language.get())));
+    let sheets = move || data_url(game.with(|g| g.match_sheets_html(language.get())));
+    let add = move |_| {
+        let mut result = Ok(0);
+        set_game.update(|g| result = g.add_bulk_results(&text.get_untracked()));
+        if result.is_ok() {
+            set_text.set(String::new());
+        }
+        set_message.set(Some(result));
+    };
+    view! {
+        <h2>{t("card.print")}</h2>
+        <p><a href=crosstable download=move || tr(language.get(), "print.crosstable_file")>{t("print.download_crosstable")}</a></p>
+        <p><a href=sheets download=move || tr(language.get(), "print.sheets_file")>{t("print.download_sheets")}</a></p>
+        <h3>{t("print.bulk")}</h3>
+        <p>{t("print.bulk_help")}</p>
+        <textarea rows="8" cols="30" prop:value=text on:input=move |ev| set_text.set(event_target_value(&ev))></textarea>
+        <p><button on:click=add>{t("print.bulk_add")}</button></p>
+        {move || message.get().map(|m| match m {
+            Ok(n) => view! { <p>{tr_n(language.get(), "print.bulk_added", n as i64)}</p> },
+            Err((line, e)) => view! {
+                <p class="error">{tr_args(language.get(), "print.bulk_line", &[&line.to_string(), &e.localized(language.get())])}</p>
+            },
+        })}
+    }
+}
+
+// Read-only full screen view for spectators. Follows the operator's tab
+// through the storage event, which fires when another tab saves the game
+#[component]
+fn Projector() -> impl IntoView {
+    let (game, set_game) = create_signal(load_game().unwrap_or_default());
+    let language = create_rw_signal(load_language().unwrap_or_default());
+    provide_context(language);
+    let listener = window_event_listener(ev::storage, move |ev| {
+        match ev.key().as_deref() {
+            Some(key) if key == STORAGE_KEY => {
+                if let Some(g) = load_game() {
+                    set_game.set(g);
+                }
+            },
+            Some(key) if key == LANGUAGE_STORAGE_KEY => language.set(load_language().unwrap_or_default()),
+            _ => (),
+        }
+    });
+    on_cleanup(move || listener.remove());
+
+    let name = move |pid: u16| game.with(|g| player_tag(g, pid));
+    let current = move || game.with(|g| g.get_next_game().map(|m| (m.player1, m.player2)));
+    let upcoming = move || game.with(|g| {
+        g.get_next_games(4).iter().skip(1).map(|m| (m.player1, m.player2)).collect::<Vec<_>>()
+    });
+    let standings = move || game.with(|g| {
+        g.get_standings().iter().map(|s| {
+            let played = g.get_player(s.pid).map(|p| p.played).unwrap_or(0);
+            (s.rank_str(), s.pid, played, s.score)
+        }).collect::<Vec<_>>()
+    });
+    let latest = move || game.with(|g| {
+        g.get_played_games().iter().rev().take(5).map(|(m, _p)| {
+            let (score1, score2) = m.get_score(g.get_mode());
+            (m.player1, m.play1, score1, m.player2, m.play2, score2)
+        }).collect::<Vec<_>>()
+    });
+    let quote = move || game.with(|g| g.get_quote());
+
+    view! {
+        <div class="projector">
+            <div class="header">
+                <h1>{t("title")}</h1>
+            </div>
+            <div class="projector_grid">
+                <div class="nnn">
+                    <h2>{t("card.games")}</h2>
+                    {move || match current() {
+                        Some((p1, p2)) => view! {
+                            <p class="projector_current">{name(p1)} " " {t("vs")} " " {name(p2)}</p>
+                        }.into_view(),
+                        None => view! { <p class="projector_current">"-"</p> }.into_view(),
+                    }}
+                    <h3>{t("schedule.on_deck")}</h3>
+                    <ol>
+                        {move || upcoming().into_iter().map(|(p1, p2)| view! {
+                            <li>{name(p1)} " " {t("vs")} " " {name(p2)}</li>
+                        }).collect_view()}
+                    </ol>
+                </div>
+                <div class="nnn">
+                    <h2>{t("card.players")}</h2>
+                    <table>
+                        <tr>
+                            <th></th>
+                            <th>{t("players.name")}</th>
+                            <th>{t("players.played")}</th>
+                            <th>{t("players.score")}</th>
+                        </tr>
+                        {move || standings().into_iter().map(|(rank, pid, played, score)| view! {
+                            <tr>
+                                <td>{rank}</td>
+                                <td>{name(pid)}</td>
+                                <td>{played}</td>
+                                <td>{score}</td>
+                            </tr>
+                        }).collect_view()}
+                    </table>
+                </div>
+                <div class="nnn">
+                    <h2>{t("projector.latest")}</h2>
+                    <table>
+                        {move || latest().into_iter().map(|(p1, play1, score1, p2, play2, score2)| view! {
+                            <tr>
+                                <td class="play">{play1.str().to_string()}</td>
+                                <td>{score1}</td>
+                                <td style="text-align:right;">{name(p1)}</td>
+                                <td>{t("vs")}</td>
+                                <td>{name(p2)}</td>
+                                <td class="play">{play2.str().to_string()}</td>
+                                <td>{score2}</td>
+                            </tr>
+                        }).collect_view()}
+                    </table>
+                    <hr/>
+                    {move || {
+                        let (quote, author) = quote();
+                        view! { <p>"\"" {quote} "\""</p><p>" - " {author}</p> }
+                    }}
+                </div>
+            </div>
+        </div>
+    }
+}
+
+// Page on a player's own device. Shows the hands while the player's
+// match is open on the throw server
+#[component]
+fn RemoteThrow() -> impl IntoView {
+    let language = create_rw_signal(load_language().unwrap_or_default());
+    provide_context(language);
+    let params = use_params_map();
+    let pid = move || params.with(|p| p.get("pid").and_then(|id| id.parse::<u16>().ok()));
+    let token = move || params.with(|p| p.get("token").cloned().unwrap_or_default());
+    let (remote, set_remote) = create_signal(None::<RemoteMatch>);
+    // Chosen hand and its nonce, kept secret until both players have committed
+    let (chosen, set_chosen) = create_signal(None::<Reveal>);
+    let (revealed, set_revealed) = create_signal(false);
+    let (offline, set_offline) = create_signal(false);
+    let (error, set_error) = create_signal(None::<GameError>);
+    // Bumped on every commit, so that an answer sent before it is not trusted
+    let commits = store_value(0);
+    let (deadline, set_deadline) = create_signal(None::<f64>);
+
+    let send_reveal = move |reveal: Reveal| spawn_local(async move {
+        match send_remote(Request::post(API_REVEAL), &reveal).await {
+            Ok(()) => set_revealed.set(true),
+            Err(Some(e)) => set_error.set(Some(e)),
+            Err(None) => set_offline.set(true),
+        }
+    });
+    let poll = move || spawn_local(async move {
+        let before = commits.get_value();
+        let (open, status) = (fetch_remote_match().await, fetch_remote_status().await);
+        set_offline.set(open.is_err() | status.is_err());
+        let (open, status) = (open.ok().flatten(), status.ok().flatten());
+        set_remote.set(open);
+        let (Some(pid), Some(s)) = (pid(), status) else {
+            return;
+        };
+        match s.countdown_left {
+            Some(left) if deadline.get_untracked().is_none() => set_deadline.set(Some(js_sys::Date::now() + left as f64)),
+            Some(_) => (),
+            None => set_deadline.set(None),
+        }
+        let (committed, done) = if pid == s.game_id.0 { (s.committed1, s.revealed1) } else { (s.committed2, s.revealed2) };
+        // The operator reopens the match when a result is undone
+        if !committed & (commits.get_value() == before) {
+            set_chosen.set(None);
+        }
+        set_revealed.set(done);
+        let mine = chosen.get_untracked().filter(|c| c.game_id == s.game_id);
+        if let Some(reveal) = mine.filter(|_| s.committed1 & s.committed2 & !done) {
+            send_reveal(reveal);
+        }
+    });
+    poll();
+    if let Ok(handle) = set_interval_with_handle(poll, POLL_INTERVAL) {
+        on_cleanup(move || handle.clear());
+    }
+    let send_commit = move |game_id: (u16, u16, u16), pid: u16, hand: Rpssl| spawn_local(async move {
+        let nonce = random_nonce(&mut rand::thread_rng());
+        let token = token();
+        let commit = Commit {game_id, pid, token: token.clone(), hash: hash(hand, &nonce)};
+        match send_remote(Request::post(API_COMMIT), &commit).await {
+            Ok(()) => {
+                commits.update_value(|n| *n += 1);
+                set_chosen.set(Some(Reveal {game_id, pid, token, hand, nonce}));
+                set_error.set(None);
+            },
+            Err(Some(e)) => set_error.set(Some(e)),
+            Err(None) => set_offline.set(true),
+        }
+    });
+
+    view! {
+        <div class="header">
+            <h1>{t("title")}</h1>
+        </div>
+        <div class="nnn remote">
+            {move || match (pid(), remote.get()) {
+                (Some(pid), Some(m)) if m.has_player(pid) => {
+                    let game_id = m.game_id;
+                    let (me, opponent) = if pid == game_id.0 { (m.player1, m.player2) } else { (m.player2, m.player1) };
+                    let countdown = m.countdown.and_then(|c| deadline.get().map(|d| (c, d)));
+                    let countdown = move || countdown.map(|(c, d)| view! { <CountdownWords countdown=c deadline=d/> });
+                    match chosen.get().filter(|c| c.game_id == game_id) {
+                        Some(c) => view! {
+                            {countdown()}
+                            <h2>{me}</h2>
+                            <p>{move || if revealed.get() {
+                                format!("✅ {}", tr(language.get(), "remote.revealed"))
+                            } else {
+                                format!("🔒 {}", tr(language.get(), "remote.locked"))
+                            }}</p>
+                            <p class="play">{c.hand.str().to_string()}</p>
+                        }.into_view(),
+                        None => view! {
+                            {countdown()}
+                            <h2>{me} " " {t("vs")} " " {opponent}</h2>
+                            <p>
+                                {m.hands.into_iter().map(|hand| view! {
+                                    <button class="hand" on:click=move |_| send_commit(game_id, pid, hand)>
+                                        {hand.str().to_string()}
+                                    </button>
+                                }).collect_view()}
+                            </p>
+                        }.into_view(),
+                    }
+                }
+                _ => view! { <p>{t("remote.wait_turn")}</p> }.into_view(),
+            }}
+            {move || offline.get().then(|| view! { <p class="error">{t("remote.offline")}</p> })}
+            {move || error.get().map(|e| view! { <p class="error">{e.localized(language.get())}</p> })}
+        </div>
+    }
+}
+
 #[component]
 fn App() -> impl IntoView {
-    let (game, set_game) = create_signal(Game::new());
-    set_game.update(|g| {let _ = g.set_mode(GameMode::RPSSL);});
-    let (show_names, set_names) = create_signal(false);
-    let (show_games, set_games) = create_signal(false);
-    let (show_options, set_options) = create_signal(false);
+    let initial = load_game().unwrap_or_else(|| {
+        let mut g = Game::new();
+        let _ = g.set_mode(GameMode::RPSSL);
+        g
+    });
+    let (game, set_game) = create_signal(initial);
+    create_effect(move |_| game.with(store_game));
+    let language = create_rw_signal(load_language().unwrap_or_default());
+    provide_context(language);
+    create_effect(move |_| store_language(language.get()));
+    let (season, set_season) = create_signal(load_season().unwrap_or_else(|| Season::new(tr(language.get_untracked(), "season.name"))));
+    create_effect(move |_| season.with(store_season));
+    // Keep the season's copy of the current tournament up to date
+    create_effect(move |_| {
+        let g = game.get();
+        set_season.update(|s| {
+            if let Some(i) = s.find_tournament(g.get_seed()) {
+                let _ = s.update_tournament(i, g);
+            }
+        });
+    });
 
     view! {
         <div class="header" id="header">
-            <h1>"Kivi-Paperi-Sakset-Lisko-Vampyyri"</h1>
+            <h1>{t("title")}</h1>
         </div>
         <div id="container">
-            <Scoring game=game/>
-            <div class="nnn" id="games" on:click=move |_| set_games.update(|value| *value = true)>
-                <Show when=move || { show_games.get() } fallback=|| view! { <h1>"Pelaamaan"</h1> }>
-                    <p class="close" on:click=move |_| set_games.update(|value| *value = false)>
-                        X
-                    </p>
-                    <MatchList game=game set_game=set_game/>
-                </Show>
-            </div>
-            <div
-                class="nnn"
-                id="player_list"
-                on:click=move |_| set_names.update(|value| *value = true) >
-                <Show when=move || { show_names.get() } fallback=|| view! { <h1>"Pelaajat"</h1> }>
-                    <p class="close" on:click=move |_| set_names.update(|value| *value = false)>
-                        X
-                    </p>
-                    <PlayerList game=game/>
-                    // <NameInput game=set_game/>
-                    <NameInput game=set_game/>
-
-                </Show>
-            </div>
-            <Rules game=game/>
-            <div class="nnn" id="options" on:click=move |_| set_options.update(|value| *value = true)>
-                <Show when=move || { show_options.get() } fallback=|| view! { <h1>"Asetukset"</h1> }>
-                    <p class="close" on:click=move |_| set_options.update(|value| *value = false)>
-                        X
-                    </p>
-                    <Setup game=game set_game=set_game/>
-                </Show>
-            </div>
+            <Card id="ordering" page=Page::Scoring title="card.scoring">
+                <Scoring game=game/>
+            </Card>
+            <Card id="games" page=Page::Play title="card.games">
+                <MatchList game=game set_game=set_game/>
+            </Card>
+            <Card id="schedule" page=Page::Schedule title="card.schedule">
+                <Schedule game=game set_game=set_game/>
+            </Card>
+            <Card id="player_list" page=Page::Players title="card.players">
+                <PlayerList game=game set_game=set_game/>
+                <NameInput game=set_game/>
+            </Card>
+            <Card id="rules" page=Page::Rules title="card.rules">
+                <Rules game=game/>
+            </Card>
+            <Card id="crosstable" page=Page::Crosstable title="card.crosstable">
+                <h2>{t("card.crosstable")}</h2>
+                <Crosstable game=game/>
+            </Card>
+            <Card id="chart" page=Page::Chart title="card.chart">
+                <StandingsChart game=game/>
+            </Card>
+            <Card id="season" page=Page::Season title="card.season">
+                <SeasonPanel game=game set_game=set_game season=season set_season=set_season/>
+            </Card>
+            <Card id="print" page=Page::Print title="card.print">
+                <Printouts game=game set_game=set_game/>
+            </Card>
+            <Card id="options" page=Page::Settings title="card.options">
+                <Setup game=game set_game=set_game/>
+            </Card>
         </div>
     }
 }
 
+// Card that is open while the address points to its page. Closing it
+// goes back to the front page
 #[component]
-pub fn Setup(
-game: ReadSignal<Game>,
-set_game: WriteSignal<Game>,
+fn Card(
+    id: &'static str,
+    page: Page,
+    title: &'static str,
+    children: ChildrenFn,
 ) -> impl IntoView {
-    let current_mode = move || match game.get().get_mode().str() {
-        "RPS" => "KPS".to_string(),
-        "RPSSL" => "KPSLV".to_string(),
-        _ => "Unknown".to_string()
-    };
-    let rounds = move || game.get().get_rounds();
+    let current = use_page();
+    let go = use_go();
+    let open = move || current.get().card() == page;
+    view! {
+        <div class="nnn" id=id on:click=move |_| if !open() { go(page) }>
+            <Show when=open fallback=move || view! { <h1>{t(title)}</h1> }>
+                <p class="close" on:click=move |ev| {
+                    ev.stop_propagation();
+                    go(Page::Home);
+                }>
+                    X
+                </p>
+                {children()}
+            </Show>
+        </div>
+    }
+}
+
+// Test players and results, only in builds with the dev feature so a
+// real tournament can't be filled with them by accident
+#[cfg(feature = "dev")]
+fn debug_button(set_game: WriteSignal<Game>) -> impl IntoView {
     let debug = move || {
         set_game.update(|g| g.set_rounds(2));
         set_game.update(|g| {let _ = g.add_player("Alice");});
@@ -520,19 +1882,245 @@ set_game: WriteSignal<Game>,
         set_game.update(|g| g.add_result((3,4,1), Rpssl::Rock, Rpssl::Paper));
     };
     view! {
-        <h2>Asetukset:</h2>
-        <button on:click=move |_| set_game.update(|game| { let _ =game.set_mode(GameMode::RPS);})>KPS</button>
-        <button on:click=move |_| set_game.update(|game| { let _ =game.set_mode(GameMode::RPSSL);})>KPSLV</button>
-        <p>"Peli: " {current_mode}</p>
-        <p>"Kierroksia: " {rounds} " "
+        <p>
+            <button on:click=move |_| debug()>{t("settings.debug")}</button>
+        </p>
+    }
+}
+
+#[cfg(not(feature = "dev"))]
+fn debug_button(_set_game: WriteSignal<Game>) -> impl IntoView {}
+
+// Organisers' own quotes, shown when the situation matches their category
+#[component]
+fn QuoteEditor(
+    game: ReadSignal<Game>,
+    set_game: WriteSignal<Game>,
+) -> impl IntoView {
+    let language = use_language();
+    let (text, set_text) = create_signal(String::new());
+    let (author, set_author) = create_signal(String::new());
+    let (category, set_category) = create_signal(QuoteCategory::General);
+    let (error, set_error) = create_signal(None::<GameError>);
+    let add = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let quote = Quote {text: text.get_untracked(), author: author.get_untracked(), category: category.get_untracked()};
+        let mut result = Ok(());
+        set_game.update(|g| result = g.add_quote(quote));
+        if result.is_ok() {
+            set_text.set(String::new());
+            set_author.set(String::new());
+        }
+        set_error.set(result.err());
+    };
+    let remove = move |index: usize| set_game.update(|g| set_error.set(g.remove_quote(index).err()));
+    view! {
+        <h3>{t("quote.own")}</h3>
+        <ul>
+            {move || game.with(|g| g.get_custom_quotes().to_vec()).into_iter().enumerate().map(|(i, q)| view! {
+                <li>
+                    "\"" {q.text} "\" - " {q.author} " (" {move || tr(language.get(), q.category.key())} ") "
+                    <span class="trashcan" on:click=move |_| remove(i)>"🗑️"</span>
+                </li>
+            }).collect_view()}
+        </ul>
+        <form on:submit=add>
+            <input type="text" placeholder=t("quote.text") prop:value=text on:input=move |ev| set_text.set(event_target_value(&ev))/>
+            " "
+            <input type="text" placeholder=t("quote.author") prop:value=author on:input=move |ev| set_author.set(event_target_value(&ev))/>
+            " "
+            <select on:change=move |ev| {
+                let value = event_target_value(&ev);
+                if let Some(c) = QuoteCategory::iter().find(|c| c.key() == value) {
+                    set_category.set(c);
+                }
+            }>
+                {QuoteCategory::iter().map(|c| view! {
+                    <option value=c.key() selected=move || category.get() == c>{move || tr(language.get(), c.key())}</option>
+                }).collect_view()}
+            </select>
+            " "
+            <input type="submit" value=t("quote.add")/>
+        </form>
+        {move || error.get().map(|e| view! { <p class="error">{e.localized(language.get())}</p> })}
+    }
+}
+
+#[component]
+pub fn Setup(
+game: ReadSignal<Game>,
+set_game: WriteSignal<Game>,
+) -> impl IntoView {
+    let language = use_language();
+    let (error, set_error) = create_signal(None::<GameError>);
+    let mode_name = move |mode: GameMode| match mode {
+        GameMode::RPS => tr(language.get(), "mode.rps"),
+        GameMode::RPSSL => tr(language.get(), "mode.rpssl"),
+    };
+    let current_mode = move || mode_name(game.get().get_mode());
+    // With results the mode is changed only after a confirmation
+    let (new_mode, set_new_mode) = create_signal(None::<GameMode>);
+    let set_mode = move |mode: GameMode| {
+        set_error.set(None);
+        if game.with_untracked(|g| g.get_played_n() == 0) {
+            set_game.update(|g| set_error.set(g.set_mode(mode).err()));
+        } else if game.with_untracked(|g| g.get_mode() != mode) {
+            set_new_mode.set(Some(mode));
+        }
+    };
+    let change_mode = move |mode: GameMode| {
+        set_new_mode.set(None);
+        set_game.update(|g| set_error.set(g.change_mode(mode, true).err()));
+    };
+    let confirm_mode = move || new_mode.get().map(|mode| {
+        let g = game.get();
+        let conflicts = g.get_mode_conflicts(mode);
+        view! {
+            <div class="confirm">
+                <p>{tr_args(language.get(), "settings.mode_change", &[mode_name(mode)])}</p>
+                <p>{t("settings.mode_rescore")}</p>
+                {(!conflicts.is_empty()).then(|| view! {
+                    <p>{t("settings.mode_void")}</p>
+                    <ul>
+                        {conflicts.iter().filter_map(|id| g.get_match(*id)).map(|m| view! {
+                            <li>
+                                {player_tag(&g, m.player1)} " " <span class="play">{m.play1.str().to_string()}</span>
+                                " " {t("vs")} " "
+                                {player_tag(&g, m.player2)} " " <span class="play">{m.play2.str().to_string()}</span>
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                })}
+                <button on:click=move |_| change_mode(mode)>{t("settings.confirm")}</button>
+                <button on:click=move |_| set_new_mode.set(None)>{t("settings.cancel")}</button>
+            </div>
+        }
+    });
+    let rounds = move || game.get().get_rounds();
+    let seed = move || game.with(|g| g.get_seed());
+    let tiebreakers = move || game.with(|g| g.get_tiebreakers());
+    // Moves a tiebreaker one step up, or adds it to the end of the chain
+    let raise = move |t: Tiebreaker| set_game.update(|g| {
+        let mut chain = g.get_tiebreakers();
+        match chain.iter().position(|c| *c == t) {
+            Some(0) => (),
+            Some(i) => chain.swap(i - 1, i),
+            None => chain.push(t),
+        }
+        g.set_tiebreakers(chain);
+    });
+    let countdown = move || game.with(|g| g.get_countdown());
+    let set_countdown = move |c: Option<Countdown>| set_game.update(|g| g.set_countdown(c));
+    let remove = move |t: Tiebreaker| set_game.update(|g| {
+        let chain = g.get_tiebreakers().into_iter().filter(|c| *c != t).collect();
+        g.set_tiebreakers(chain);
+    });
+    // Removing a round with results needs a second click
+    let (round_warning, set_round_warning) = create_signal(false);
+    let remove_round = move |discard: bool| {
+        set_round_warning.set(false);
+        let mut result = Ok(vec![]);
+        set_game.update(|g| result = g.remove_rounds(discard));
+        // Played matches are listed in the confirmation instead of an error
+        match result {
+            Err(GameError::RoundPlayed(_)) => {
+                set_round_warning.set(true);
+                set_error.set(None);
+            },
+            result => set_error.set(result.err()),
+        }
+    };
+    let confirm_round = move || round_warning.get().then(|| {
+        let g = game.get();
+        view! {
+            <div class="confirm">
+                <p>{t("settings.round_discard")}</p>
+                <ul>
+                    {g.get_round_results(g.get_rounds()).into_iter().filter_map(|id| g.get_match(id)).map(|m| view! {
+                        <li>
+                            {player_tag(&g, m.player1)} " " <span class="play">{m.play1.str().to_string()}</span>
+                            " " {t("vs")} " "
+                            {player_tag(&g, m.player2)} " " <span class="play">{m.play2.str().to_string()}</span>
+                        </li>
+                    }).collect_view()}
+                </ul>
+                <button on:click=move |_| remove_round(true)>{t("settings.confirm")}</button>
+                <button on:click=move |_| set_round_warning.set(false)>{t("settings.cancel")}</button>
+            </div>
+        }
+    });
+    let (confirm_empty, set_confirm_empty) = create_signal(false);
+    let empty = move || {
+        set_confirm_empty.set(false);
+        set_game.update(|g| set_error.set(g.empty().err()));
+    };
+    view! {
+        <h2>{t("settings.title")}</h2>
+        <p>{t("settings.language")} ": "
+            {Language::iter().map(|l| view! {
+                <button class:selected=move || language.get() == l on:click=move |_| language.set(l)>
+                    {l.name().to_string()}
+                </button>
+            }).collect_view()}
+        </p>
+        <button on:click=move |_| set_mode(GameMode::RPS)>{t("mode.rps")}</button>
+        <button on:click=move |_| set_mode(GameMode::RPSSL)>{t("mode.rpssl")}</button>
+        {confirm_mode}
+        {move || error.get().map(|e| view! { <p class="error">{e.localized(language.get())}</p> })}
+        <p>{t("settings.mode")} ": " {current_mode}</p>
+        <p>{t("settings.seed")} ": " {seed}</p>
+        <p>{t("settings.rounds")} ": " {rounds} " "
             <button on:click=move |_| set_game.update(|game| { let _ =game.add_rounds();})>+</button>
-            <button on:click=move |_| set_game.update(|game| { let _ =game.remove_rounds();})>-</button>
+            <button on:click=move |_| remove_round(false)>-</button>
             </p>
+        {confirm_round}
+        <p>{t("settings.tiebreakers")} ":"</p>
+        <ol>
+            {move || tiebreakers().into_iter().map(|t| view! {
+                <li>
+                    {move || tr(language.get(), tiebreaker_key(t))} " "
+                    <button on:click=move |_| raise(t)>"↑"</button>
+                    <button on:click=move |_| remove(t)>"✕"</button>
+                </li>
+            }).collect_view()}
+        </ol>
         <p>
-            <button on:click=move |_| debug()>Debug</button>
+            {move || Tiebreaker::iter().filter(|t| !tiebreakers().contains(t)).map(|t| view! {
+                <button on:click=move |_| raise(t)>"+ " {move || tr(language.get(), tiebreaker_key(t))}</button>
+            }).collect_view()}
         </p>
+        <p>{t("settings.countdown")} ": "
+            <button class:selected=move || countdown().is_none() on:click=move |_| set_countdown(None)>{t("countdown.off")}</button>
+            <button class:selected=move || countdown().is_some() on:click=move |_| set_countdown(Some(countdown().unwrap_or_default()))>{t("countdown.on")}</button>
+        </p>
+        {move || countdown().map(|c| view! {
+            <p>{t("countdown.tempo")} ": " {c.beat_ms} " ms "
+                <button on:click=move |_| set_countdown(Some(Countdown {beat_ms: (c.beat_ms + BEAT_STEP).min(MAX_BEAT), ..c}))>+</button>
+                <button on:click=move |_| set_countdown(Some(Countdown {beat_ms: c.beat_ms.saturating_sub(BEAT_STEP).max(MIN_BEAT), ..c}))>-</button>
+            </p>
+            <p>{t("countdown.penalty")} ": "
+                <button class:selected=c.penalty == LatePenalty::Loss on:click=move |_| set_countdown(Some(Countdown {penalty: LatePenalty::Loss, ..c}))>
+                    {t("countdown.loss")}
+                </button>
+                <button class:selected=c.penalty == LatePenalty::RandomThrow on:click=move |_| set_countdown(Some(Countdown {penalty: LatePenalty::RandomThrow, ..c}))>
+                    {t("countdown.random")}
+                </button>
+            </p>
+        })}
+        <QuoteEditor game=game set_game=set_game/>
         <p>
-            <button on:click=move |_| set_game.update(|game| {let _ = game.empty();})>Empty</button>
+            <a href=PROJECTOR_PATH target="_blank">{t("settings.projector")}</a>
         </p>
+        {debug_button(set_game)}
+        <p>
+            <button on:click=move |_| set_confirm_empty.set(true)>{t("settings.empty")}</button>
+        </p>
+        {move || confirm_empty.get().then(|| view! {
+            <div class="confirm">
+                <p>{t("settings.empty_confirm")}</p>
+                <button on:click=move |_| empty()>{t("settings.confirm")}</button>
+                <button on:click=move |_| set_confirm_empty.set(false)>{t("settings.cancel")}</button>
+            </div>
+        })}
     }
 }
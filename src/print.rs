@@ -0,0 +1,215 @@
+use std::collections::BTreeSet;
+use crate::{Game, Playable, Rpssl};
+use crate::error::GameError;
+use crate::i18n::{lookup, tr, tr_args, Language};
+
+// Every match sheet starts on its own page
+static STYLE: &str = "
+body { font-family: sans-serif; }
+table { border-collapse: collapse; }
+td, th { border: 1px solid black; padding: 0.4em; text-align: center; }
+.crosstable td { width: 5em; height: 3em; }
+.diagonal { background: #ccc; }
+.sheet { page-break-after: always; margin-bottom: 2em; }
+.sheet td { text-align: left; }
+.score { width: 5em; }
+";
+
+// Match and the hands read from one line of bulk entry
+pub type BulkResult = ((u16, u16, u16), Rpssl, Rpssl);
+
+// Box size of one hand on a match sheet
+static BOX_WIDTH: usize = 90;
+static BOX_HEIGHT: usize = 60;
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn document(language: Language, title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        language.code(), escape(title), STYLE, body,
+    )
+}
+
+fn hand_name(language: Language, hand: Rpssl) -> &'static str {
+    lookup(language, &format!("hand.{}", hand.name().to_lowercase())).unwrap_or("?")
+}
+
+// Code of a match on its sheet, typed back in with the hands
+pub fn match_code(game_id: (u16, u16, u16)) -> String {
+    format!("{}-{}-{}", game_id.0, game_id.1, game_id.2)
+}
+
+fn parse_code(code: &str) -> Option<(u16, u16, u16)> {
+    let mut parts = code.split('-').map(|p| p.parse::<u16>().ok());
+    match (parts.next()?, parts.next()?, parts.next()?, parts.next()) {
+        (Some(p1), Some(p2), Some(round), None) => Some((p1, p2, round)),
+        _ => None,
+    }
+}
+
+// One box per hand with its shortcut, for circling the hand thrown
+fn hand_boxes(language: Language, hands: &[Rpssl]) -> String {
+    let width = BOX_WIDTH * hands.len();
+    let boxes = hands.iter().enumerate().map(|(i, hand)| {
+        let x = i * BOX_WIDTH;
+        format!(
+            "<rect x=\"{}\" y=\"4\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"black\"/>\
+             <text x=\"{}\" y=\"28\" font-size=\"20\" text-anchor=\"middle\">{}</text>\
+             <text x=\"{}\" y=\"48\" font-size=\"12\" text-anchor=\"middle\">{} ({})</text>",
+            x + 4, BOX_WIDTH - 8, BOX_HEIGHT - 8,
+            x + BOX_WIDTH / 2, hand.str(),
+            x + BOX_WIDTH / 2, hand_name(language, *hand), hand.shortcut(),
+        )
+    }).collect::<String>();
+    format!("<svg width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">{}</svg>", width, BOX_HEIGHT, width, BOX_HEIGHT, boxes)
+}
+
+impl Game {
+    fn print_name(&self, pid: u16) -> String {
+        escape(self.get_player(pid).as_ref().map_or("", |p| p.display_name()))
+    }
+
+    // Round robin table with results so far and empty cells for the rest
+    pub fn crosstable_html(&self, language: Language) -> String {
+        let table = self.get_crosstable();
+        let mut body = format!("<h1>{}</h1>\n<table class=\"crosstable\">\n<tr><th></th>", escape(tr(language, "card.crosstable")));
+        for &p in &table.players {
+            body += &format!("<th>{}</th>", self.print_name(p));
+        }
+        body += &format!("<th>{}</th></tr>\n", escape(tr(language, "players.score")));
+        for &p1 in &table.players {
+            body += &format!("<tr><th>{}</th>", self.print_name(p1));
+            for &p2 in &table.players {
+                body += &match table.get(p1, p2) {
+                    None => "<td class=\"diagonal\"></td>".to_string(),
+                    Some(h2h) if h2h.played == 0 => "<td></td>".to_string(),
+                    Some(h2h) => format!(
+                        "<td>{}-{}-{}<br>{}:{}</td>",
+                        h2h.wins, h2h.draws, h2h.losses, h2h.points, h2h.points_against,
+                    ),
+                };
+            }
+            body += &format!("<td>{}</td></tr>\n", self.get_player(p1).map_or(0, |p| p.score));
+        }
+        body += "</table>\n";
+        document(language, tr(language, "card.crosstable"), &body)
+    }
+
+    // One sheet per unplayed match in schedule order
+    pub fn match_sheets_html(&self, language: Language) -> String {
+        let hands = self.game_mode.hands();
+        let mut body = String::new();
+        for m in self.get_schedule() {
+            let game_id = (m.player1, m.player2, m.round);
+            body += &format!(
+                "<div class=\"sheet\">\n<h2>{} {}</h2>\n<p>{}</p>\n<table>\n",
+                escape(tr(language, "print.match")), match_code(game_id),
+                escape(&tr_args(language, "schedule.round", &[&m.round.to_string()])),
+            );
+            for pid in [m.player1, m.player2] {
+                let is_bot = self.get_player(pid).is_some_and(|p| p.is_bot());
+                let boxes = if is_bot { "🤖".to_string() } else { hand_boxes(language, &hands) };
+                body += &format!("<tr><td>{}</td><td>{}</td><td class=\"score\"></td></tr>\n", self.print_name(pid), boxes);
+            }
+            body += "</table>\n</div>\n";
+        }
+        document(language, tr(language, "print.sheets"), &body)
+    }
+
+    // Results typed in from match sheets, one "code hand hand" line per
+    // match, e.g. "1-2-1 r p". Bots throw for themselves and get "-".
+    // Errors come with the line number
+    pub fn parse_bulk_results(&self, text: &str) -> Result<Vec<BulkResult>, (usize, GameError)> {
+        let mut results = vec![];
+        let mut seen = BTreeSet::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() | line.starts_with('#') {
+                continue;
+            }
+            let error = |e: GameError| (i + 1, e);
+            let [code, hand1, hand2] = line.split_whitespace().collect::<Vec<_>>()[..] else {
+                return Err(error(GameError::UnreadableLine));
+            };
+            let game_id = parse_code(code).ok_or(error(GameError::UnreadableLine))?;
+            let m = self.match_list.get(&game_id).ok_or(error(GameError::NoSuchGame))?;
+            if m.result.is_some() | !seen.insert(game_id) {
+                return Err(error(GameError::AlreadyPlayed));
+            }
+            let hand = |pid: u16, token: &str| {
+                if self.get_player(pid).is_some_and(|p| p.is_bot()) {
+                    return Ok(Rpssl::None);
+                }
+                let mut chars = token.chars();
+                match (chars.next(), chars.next()) {
+                    (Some('-'), None) => Err(error(GameError::MissingHands)),
+                    (Some(c), None) => Rpssl::from_shortcut(c)
+                        .filter(|h| self.game_mode.hands().contains(h))
+                        .ok_or(error(GameError::InvalidHand)),
+                    _ => Err(error(GameError::UnreadableLine)),
+                }
+            };
+            results.push((game_id, hand(m.player1, hand1)?, hand(m.player2, hand2)?));
+        }
+        Ok(results)
+    }
+
+    // Adds all results of the text, or none of them if a line is wrong.
+    // Returns the number of results added
+    pub fn add_bulk_results(&mut self, text: &str) -> Result<usize, (usize, GameError)> {
+        let results = self.parse_bulk_results(text)?;
+        for &(game_id, play1, play2) in &results {
+            if play1.is_none() | play2.is_none() {
+                self.add_result_with_bots(game_id, play1, play2);
+            } else {
+                self.add_result(game_id, play1, play2);
+            }
+        }
+        Ok(results.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::StrategyKind;
+
+    #[test]
+    fn print() {
+        let mut game = Game::with_seed(1);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("<Bob>");
+        let _ = game.add_bot("Robo", StrategyKind::Random);
+        game.add_result((1, 2, 1), Rpssl::Rock, Rpssl::Scissors);
+
+        let table = game.crosstable_html(Language::En);
+        assert!(table.starts_with("<!DOCTYPE html>"));
+        assert!(table.contains("&lt;Bob&gt;"));
+        assert!(table.contains("1-0-0"));
+
+        let sheets = game.match_sheets_html(Language::Fi);
+        // The played match gets no sheet
+        assert!(!sheets.contains("1-2-1"));
+        assert_eq!(sheets.matches("class=\"sheet\"").count(), 2);
+        assert!(sheets.contains("Kivi (r)"));
+
+        assert_eq!(game.parse_bulk_results("1-3-1 r"), Err((1, GameError::UnreadableLine)));
+        assert_eq!(game.parse_bulk_results("# comment\n1-2-1 r p"), Err((2, GameError::AlreadyPlayed)));
+        assert_eq!(game.parse_bulk_results("1-3-1 x -"), Err((1, GameError::InvalidHand)));
+        assert_eq!(game.parse_bulk_results("2-3-1 p -\n2-3-1 p -"), Err((2, GameError::AlreadyPlayed)));
+        // Nothing is added when a line is wrong
+        assert!(game.add_bulk_results("1-3-1 p -\n9-9-9 r r").is_err());
+        assert!(game.get_match((1, 3, 1)).unwrap().result.is_none());
+
+        assert_eq!(game.add_bulk_results("1-3-1 p -\n\n2-3-1 S x"), Ok(2));
+        assert_eq!(game.get_match((1, 3, 1)).unwrap().play1, Rpssl::Paper);
+        assert_eq!(game.get_match((2, 3, 1)).unwrap().play1, Rpssl::Scissors);
+        assert_eq!(game.get_left_n(), 0);
+    }
+}
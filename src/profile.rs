@@ -0,0 +1,99 @@
+use serde::{Serialize, Deserialize};
+use crate::{Action, Game, Player};
+use crate::error::GameError;
+
+// Uploaded avatars are kept in the saved game, so they must stay small
+pub static MAX_AVATAR_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Avatar {
+    Emoji(String),
+    // Uploaded picture as a data:image/... URL
+    Image(String),
+}
+
+// Optional extras shown next to the player's name
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Profile {
+    pub nickname: Option<String>,
+    // CSS colour as #rrggbb
+    pub colour: Option<String>,
+    pub avatar: Option<Avatar>,
+    pub catchphrase: Option<String>,
+}
+
+impl Profile {
+    pub fn validate(&self) -> Result<(), GameError> {
+        if let Some(colour) = &self.colour {
+            let hex = colour.strip_prefix('#').unwrap_or("");
+            if (hex.len() != 6) | !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(GameError::InvalidColour);
+            }
+        }
+        if let Some(Avatar::Image(url)) = &self.avatar {
+            if !url.starts_with("data:image/") | (url.len() > MAX_AVATAR_BYTES) {
+                return Err(GameError::InvalidAvatar);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Player {
+    // Nickname if the player has one
+    pub fn display_name(&self) -> &str {
+        self.profile.nickname.as_deref().unwrap_or(&self.name)
+    }
+}
+
+impl Game {
+    // Empty texts are stored as None
+    pub fn set_profile(&mut self, pid: u16, profile: Profile) -> Result<(), GameError> {
+        profile.validate()?;
+        let player = self.player_list.get_mut(&pid).ok_or(GameError::NoSuchPlayer)?;
+        let text = |s: Option<String>| s.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        let profile = Profile {
+            nickname: text(profile.nickname),
+            catchphrase: text(profile.catchphrase),
+            avatar: profile.avatar.filter(|a| !matches!(a, Avatar::Emoji(e) if e.trim().is_empty())),
+            ..profile
+        };
+        player.profile = profile.clone();
+        self.actions.push(Action::SetProfile(pid, profile));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile() {
+        let mut game = Game::with_seed(1);
+        let _ = game.add_player("Alice");
+        assert_eq!(game.get_player(1).unwrap().display_name(), "Alice");
+
+        let bad_colour = Profile {colour: Some("red".to_string()), ..Default::default()};
+        assert_eq!(game.set_profile(1, bad_colour), Err(GameError::InvalidColour));
+        let not_image = Profile {avatar: Some(Avatar::Image("https://example.com/a.png".to_string())), ..Default::default()};
+        assert_eq!(game.set_profile(1, not_image), Err(GameError::InvalidAvatar));
+        let too_large = Profile {avatar: Some(Avatar::Image(format!("data:image/png;base64,{}", "A".repeat(MAX_AVATAR_BYTES)))), ..Default::default()};
+        assert_eq!(game.set_profile(1, too_large), Err(GameError::InvalidAvatar));
+        assert_eq!(game.set_profile(2, Profile::default()), Err(GameError::NoSuchPlayer));
+
+        let profile = Profile {
+            nickname: Some(" Ace ".to_string()),
+            colour: Some("#FF6666".to_string()),
+            avatar: Some(Avatar::Emoji("🦊".to_string())),
+            catchphrase: Some("".to_string()),
+        };
+        assert!(game.set_profile(1, profile).is_ok());
+        let alice = game.get_player(1).unwrap();
+        assert_eq!(alice.display_name(), "Ace");
+        assert_eq!(alice.profile.catchphrase, None);
+
+        let replayed = Game::from_saved(&game.save()).unwrap();
+        assert_eq!(replayed.get_player(1).unwrap().profile, alice.profile);
+    }
+}
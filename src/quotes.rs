@@ -0,0 +1,189 @@
+use serde::{Serialize, Deserialize};
+use strum_macros::EnumIter;
+use crate::{Action, Game, RpsResult};
+use crate::error::GameError;
+use crate::standings::Surprise;
+
+// Situations a quote fits. General quotes are used when nothing special
+// happened, or when a situation has no quotes of its own
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter, Serialize, Deserialize)]
+pub enum QuoteCategory {
+    General,
+    // No results yet
+    Start,
+    // The winner had fewer points than the loser before the match
+    Upset,
+    // The player ranked first before the match lost
+    LeaderBeaten,
+    Draw,
+    // The last match of the tournament has been played
+    Final,
+}
+
+impl QuoteCategory {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::General => "quote.general",
+            Self::Start => "quote.start",
+            Self::Upset => "quote.upset",
+            Self::LeaderBeaten => "quote.leader_beaten",
+            Self::Draw => "quote.draw",
+            Self::Final => "quote.final",
+        }
+    }
+}
+
+// Quote added by the organisers, kept in the saved game
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Quote {
+    pub text: String,
+    pub author: String,
+    pub category: QuoteCategory,
+}
+
+static BUILTIN: &[(QuoteCategory, &str, &str)] = &[
+    (QuoteCategory::General, "Victory belongs to the most persevering.", "Napoleon Bonaparte"),
+    (QuoteCategory::General, "Your victory is right around the corner. Never give up.", "Nicky Minaj"),
+    (QuoteCategory::General, "War is a series of catastrophes which result in victory.", "Albert Pike"),
+    (QuoteCategory::General, "Know thy self, know thy enemy. A thousand battles, a thousand victories", "Sun Tzu"),
+    (QuoteCategory::General, "No victory without suffering", "J. R. R. Tolkien"),
+    (QuoteCategory::General, "Victory comes from finding opportunities in problems", "Sun Tzu"),
+    (QuoteCategory::General, "Without training, they lacked knowledge. Without knowledge, they lacked confidence. Without confidence, they lacked victory.", "Julius Ceasar"),
+    (QuoteCategory::General, "The will to conquer is the first condition of victory", "Ferdinand Foch"),
+    (QuoteCategory::General, "The more difficult the victory, the greater the happiness in winning", "Pele"), //TODO!
+    (QuoteCategory::General, "Preparedness is the key to success and victory", "Douglas MacArthur"),
+    (QuoteCategory::General, "So a military force has no constant formation, water has no constant shape: the ability to gain victory by changing and adapting according to the opponent is called genius.", "Sun Tzu"),
+    (QuoteCategory::General, "Even the smallest victory is never to be taken for granted. Each victory must be applauded...", "Audre Lorde"),
+    (QuoteCategory::General, "Victory is sweetest when you've known defeat.", "Malcolm Forbes"),
+    (QuoteCategory::General, "You ask what the aim is? I tell you it is victory - total victory.", "Winston Churchill "),
+    (QuoteCategory::General, "Forewarned, forearmed; to be prepared is half the victory.","Miguel de Cervantes"),
+    (QuoteCategory::General, "Full effort is full victory.","Mahatma Gandhi"),
+    (QuoteCategory::General, "If there exists no possibility of failure, then victory is meaningless.","Robert H. Schuller"),
+    (QuoteCategory::General, "We lost because we told ourselves we lost.","Leo Tolstoy"),
+    (QuoteCategory::General, "The secret of all victory lies in the organization of the non-obvious.", "Marcus Aurelius"),
+    (QuoteCategory::General, "I would challenge you to a battle of wits, but I see you are unarmed!","William Shakespeare"),
+    (QuoteCategory::General, "Sometimes by losing a battle you find a new way to win the war.","Donald Trump"),
+    (QuoteCategory::General, "All men can see these tactics whereby I conquer, but what none can see is the strategy out of which victory is evolved.","Sun Tzu"),
+    (QuoteCategory::General, "Our greatest glory is not in never falling, but in rising every time we fall.", "Confucius"),
+    (QuoteCategory::General, "Somebody's gotta win and somebody's gotta lose and I believe in letting the other guy lose.", "Pete Rose"),
+    (QuoteCategory::Start, "The journey of a thousand miles begins with one step.", "Lao Tzu"),
+    (QuoteCategory::Start, "Well begun is half done.", "Aristotle"),
+    (QuoteCategory::Start, "Hyvin suunniteltu on puoliksi tehty.", "Sananlasku"),
+    (QuoteCategory::Upset, "The bigger they are, the harder they fall.", "Bob Fitzsimmons"),
+    (QuoteCategory::Upset, "It always seems impossible until it's done.", "Nelson Mandela"),
+    (QuoteCategory::LeaderBeaten, "Uneasy lies the head that wears a crown.", "William Shakespeare"),
+    (QuoteCategory::LeaderBeaten, "Pride goeth before destruction, and an haughty spirit before a fall.", "Proverbs 16:18"),
+    (QuoteCategory::Draw, "Great minds think alike.", "Proverb"),
+    (QuoteCategory::Draw, "Kaksi kovaa ei hyvää jauha.", "Sananlasku"),
+    (QuoteCategory::Final, "There is only one decisive victory: the last.", "Carl von Clausewitz"),
+    (QuoteCategory::Final, "It ain't over till it's over.", "Yogi Berra"),
+];
+
+impl Game {
+    pub fn add_quote(&mut self, quote: Quote) -> Result<(), GameError> {
+        let quote = Quote {text: quote.text.trim().to_string(), author: quote.author.trim().to_string(), ..quote};
+        if quote.text.is_empty() {
+            return Err(GameError::EmptyQuote);
+        }
+        self.quotes.push(quote.clone());
+        self.actions.push(Action::AddQuote(quote));
+        Ok(())
+    }
+
+    pub fn remove_quote(&mut self, index: usize) -> Result<(), GameError> {
+        if index >= self.quotes.len() {
+            return Err(GameError::NoSuchQuote);
+        }
+        self.quotes.remove(index);
+        self.actions.push(Action::RemoveQuote(index));
+        Ok(())
+    }
+
+    pub fn get_custom_quotes(&self) -> &[Quote] {
+        &self.quotes
+    }
+
+    // What the latest result meant, judged from the standings just before it
+    pub fn get_quote_category(&self) -> QuoteCategory {
+        let Some((last, _)) = self.get_played_games().last().copied() else {
+            return QuoteCategory::Start;
+        };
+        if self.get_left_n() == 0 {
+            return QuoteCategory::Final;
+        }
+        let (winner, loser) = match last.result {
+            Some(RpsResult::Win) => (last.player1, last.player2),
+            Some(RpsResult::Lose) => (last.player2, last.player1),
+            _ => return QuoteCategory::Draw,
+        };
+        // The last result is the last step of the history
+        let history = self.get_standings_history();
+        match history.surprise(winner, loser, history.matches.len() - 1) {
+            Some(Surprise::LeaderBeaten) => QuoteCategory::LeaderBeaten,
+            Some(Surprise::Upset) => QuoteCategory::Upset,
+            None => QuoteCategory::General,
+        }
+    }
+
+    // Quote and author for the situation. The pick changes with every
+    // result, and the organisers' own quotes are in the same pool
+    pub fn get_quote(&self) -> (String, String) {
+        let pool = |category: QuoteCategory| BUILTIN.iter()
+            .filter(|(c, ..)| *c == category)
+            .map(|(_, text, author)| (text.to_string(), author.to_string()))
+            .chain(self.quotes.iter()
+                .filter(|q| q.category == category)
+                .map(|q| (q.text.clone(), q.author.clone())))
+            .collect::<Vec<_>>();
+        let mut quotes = pool(self.get_quote_category());
+        if quotes.is_empty() {
+            quotes = pool(QuoteCategory::General);
+        }
+        quotes[self.quote_index % quotes.len()].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rpssl;
+
+    #[test]
+    fn quotes() {
+        let mut game = Game::with_seed(1);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_player("Charlie");
+        let _ = game.add_player("Dave");
+        assert_eq!(game.get_quote_category(), QuoteCategory::Start);
+        let start = game.get_quote();
+        assert!(BUILTIN.iter().any(|(c, text, _)| (*c == QuoteCategory::Start) & (*text == start.0)));
+
+        game.add_result((1, 2, 1), Rpssl::Rock, Rpssl::Rock);
+        assert_eq!(game.get_quote_category(), QuoteCategory::Draw);
+        // Alice and Bob share the lead
+        game.add_result((2, 3, 1), Rpssl::Rock, Rpssl::Paper);
+        assert_eq!(game.get_quote_category(), QuoteCategory::LeaderBeaten);
+        // Charlie leads now, Bob is ahead of Dave
+        game.add_result((2, 4, 1), Rpssl::Scissors, Rpssl::Rock);
+        assert_eq!(game.get_quote_category(), QuoteCategory::Upset);
+        game.add_result((3, 4, 1), Rpssl::Rock, Rpssl::Scissors);
+        assert_eq!(game.get_quote_category(), QuoteCategory::General);
+        game.add_result((1, 3, 1), Rpssl::Rock, Rpssl::Rock);
+        game.add_result((1, 4, 1), Rpssl::Rock, Rpssl::Rock);
+        assert_eq!(game.get_quote_category(), QuoteCategory::Final);
+
+        assert_eq!(game.add_quote(Quote {text: " ".to_string(), author: "".to_string(), category: QuoteCategory::Final}), Err(GameError::EmptyQuote));
+        assert_eq!(game.remove_quote(0), Err(GameError::NoSuchQuote));
+        let own = Quote {text: "Kivi voittaa aina".to_string(), author: "Pepulo".to_string(), category: QuoteCategory::Final};
+        assert!(game.add_quote(own.clone()).is_ok());
+        let finals = (0..100).map(|i| Game {quote_index: i, ..game.clone()}.get_quote()).collect::<Vec<_>>();
+        assert!(finals.contains(&(own.text.clone(), own.author.clone())));
+
+        let replayed = Game::from_saved(&game.save()).unwrap();
+        assert_eq!(replayed.get_custom_quotes(), &[own]);
+        assert_eq!(replayed.get_quote(), game.get_quote());
+        assert!(game.remove_quote(0).is_ok());
+        assert!(game.get_custom_quotes().is_empty());
+    }
+}
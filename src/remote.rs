@@ -0,0 +1,350 @@
+use serde::{Serialize, Deserialize};
+use crate::{Game, Rpssl};
+use crate::commit::Commitment;
+use crate::countdown::Countdown;
+use crate::error::GameError;
+
+// Paths of the throw server API. The web UI reaches them through the
+// Trunk proxy, so they are relative to the UI address
+pub static API_MATCH: &str = "/api/match";
+pub static API_COMMIT: &str = "/api/commit";
+pub static API_REVEAL: &str = "/api/reveal";
+pub static API_START: &str = "/api/start";
+pub static API_STATUS: &str = "/api/status";
+// Header with the operator secret, needed to open, close and start matches
+pub static OPERATOR_HEADER: &str = "x-operator-secret";
+
+// Match opened for throws from the players' own devices
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteMatch {
+    pub game_id: (u16, u16, u16),
+    pub player1: String,
+    pub player2: String,
+    // Bots throw in the operator's tab, only humans send a hand
+    pub human1: bool,
+    pub human2: bool,
+    pub hands: Vec<Rpssl>,
+    pub countdown: Option<Countdown>,
+}
+
+impl RemoteMatch {
+    pub fn new(game: &Game, game_id: (u16, u16, u16)) -> Result<RemoteMatch, GameError> {
+        let m = game.get_match(game_id).ok_or(GameError::NoSuchGame)?;
+        if m.result.is_some() {
+            return Err(GameError::AlreadyPlayed);
+        }
+        let player1 = game.get_player(m.player1).ok_or(GameError::NoSuchPlayer)?;
+        let player2 = game.get_player(m.player2).ok_or(GameError::NoSuchPlayer)?;
+        Ok(RemoteMatch {
+            game_id,
+            player1: player1.name.clone(),
+            player2: player2.name.clone(),
+            human1: !player1.is_bot(),
+            human2: !player2.is_bot(),
+            hands: game.get_mode().hands(),
+            countdown: game.get_countdown(),
+        })
+    }
+
+    pub fn has_player(&self, pid: u16) -> bool {
+        (self.human1 & (pid == self.game_id.0)) | (self.human2 & (pid == self.game_id.1))
+    }
+}
+
+// Sent by the operator to open a match. The tokens are in the players'
+// links and must not be shown to others, so they are kept out of RemoteMatch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenMatch {
+    pub remote: RemoteMatch,
+    pub tokens: (String, String),
+}
+
+// First step of a throw: only the hash of the hand and a nonce is sent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commit {
+    pub game_id: (u16, u16, u16),
+    pub pid: u16,
+    pub token: String,
+    pub hash: String,
+}
+
+// Second step, once both players have committed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reveal {
+    pub game_id: (u16, u16, u16),
+    pub pid: u16,
+    pub token: String,
+    pub hand: Rpssl,
+    pub nonce: String,
+}
+
+// What the operator sees of the open match. Commitments are only shown
+// once every human has revealed or is late
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteStatus {
+    pub game_id: (u16, u16, u16),
+    pub committed1: bool,
+    pub committed2: bool,
+    pub revealed1: bool,
+    pub revealed2: bool,
+    // Did not commit before the countdown ended
+    pub late1: bool,
+    pub late2: bool,
+    // Time left in a started countdown. Clocks of the devices differ, so
+    // they count down from this instead of a shared start time
+    pub countdown_left: Option<u64>,
+    pub commitments: Option<(Option<Commitment>, Option<Commitment>)>,
+}
+
+impl RemoteStatus {
+    pub fn hands(&self) -> Option<(Rpssl, Rpssl)> {
+        let hand = |c: &Option<Commitment>| c.as_ref().map_or(Rpssl::None, |c| c.hand);
+        self.commitments.as_ref().map(|(c1, c2)| (hand(c1), hand(c2)))
+    }
+}
+
+// Pending throws of the open match, kept by the throw server. Times are
+// milliseconds of the server clock
+#[derive(Debug, Clone)]
+pub struct ThrowBox {
+    // Only the operator knows it, players can't open matches
+    secret: String,
+    open: Option<RemoteMatch>,
+    tokens: (String, String),
+    hashes: (Option<String>, Option<String>),
+    reveals: (Option<Commitment>, Option<Commitment>),
+    started: Option<u64>,
+}
+
+impl ThrowBox {
+    pub fn new(secret: &str) -> ThrowBox {
+        ThrowBox {
+            secret: secret.to_string(),
+            open: None,
+            tokens: (String::new(), String::new()),
+            hashes: (None, None),
+            reveals: (None, None),
+            started: None,
+        }
+    }
+
+    fn check_secret(&self, secret: &str) -> Result<(), GameError> {
+        if self.secret.is_empty() | (secret != self.secret) {
+            return Err(GameError::NotOperator);
+        }
+        Ok(())
+    }
+
+    // Opens a match for throws. Hands thrown so far are dropped
+    pub fn open(&mut self, secret: &str, open: OpenMatch) -> Result<(), GameError> {
+        self.check_secret(secret)?;
+        *self = ThrowBox {open: Some(open.remote), tokens: open.tokens, ..ThrowBox::new(secret)};
+        Ok(())
+    }
+
+    pub fn close(&mut self, secret: &str, game_id: (u16, u16, u16)) -> Result<(), GameError> {
+        self.check_secret(secret)?;
+        if self.open.as_ref().is_some_and(|m| m.game_id == game_id) {
+            *self = ThrowBox::new(secret);
+        }
+        Ok(())
+    }
+
+    pub fn get_open(&self) -> Option<&RemoteMatch> {
+        self.open.as_ref()
+    }
+
+    // Starts the countdown of the open match. Hands are locked when it ends
+    pub fn start(&mut self, secret: &str, game_id: (u16, u16, u16), now: u64) -> Result<(), GameError> {
+        self.check_secret(secret)?;
+        if !self.open.as_ref().is_some_and(|m| (m.game_id == game_id) & m.countdown.is_some()) {
+            return Err(GameError::NoSuchGame);
+        }
+        self.started.get_or_insert(now);
+        Ok(())
+    }
+
+    fn deadline(&self) -> Option<u64> {
+        let countdown = self.open.as_ref()?.countdown?;
+        Some(self.started? + countdown.duration_ms())
+    }
+
+    // Whether the player is side 1 of the open match. The token must be
+    // the one in the player's link
+    fn side(&self, game_id: (u16, u16, u16), pid: u16, token: &str) -> Result<bool, GameError> {
+        let remote = self.open.as_ref()
+            .filter(|m| m.game_id == game_id)
+            .ok_or(GameError::NoSuchGame)?;
+        if !remote.has_player(pid) {
+            return Err(GameError::NoSuchPlayer);
+        }
+        let first = pid == game_id.0;
+        let expected = if first { &self.tokens.0 } else { &self.tokens.1 };
+        if expected.is_empty() | (token != expected) {
+            return Err(GameError::BadToken);
+        }
+        Ok(first)
+    }
+
+    pub fn commit(&mut self, commit: Commit, now: u64) -> Result<(), GameError> {
+        let first = self.side(commit.game_id, commit.pid, &commit.token)?;
+        if self.deadline().is_some_and(|d| now > d) {
+            return Err(GameError::TooLate);
+        }
+        let hash = if first { &mut self.hashes.0 } else { &mut self.hashes.1 };
+        if hash.is_some() {
+            return Err(GameError::AlreadyThrown);
+        }
+        *hash = Some(commit.hash);
+        Ok(())
+    }
+
+    pub fn reveal(&mut self, reveal: Reveal, now: u64) -> Result<(), GameError> {
+        let first = self.side(reveal.game_id, reveal.pid, &reveal.token)?;
+        let status = self.status(now).ok_or(GameError::NoSuchGame)?;
+        if !(status.committed1 | status.late1) | !(status.committed2 | status.late2) {
+            return Err(GameError::NotCommitted);
+        }
+        if !self.open.as_ref().is_some_and(|m| m.hands.contains(&reveal.hand)) {
+            return Err(GameError::InvalidHand);
+        }
+        let (hash, revealed) = if first {
+            (&self.hashes.0, &mut self.reveals.0)
+        } else {
+            (&self.hashes.1, &mut self.reveals.1)
+        };
+        if revealed.is_some() {
+            return Err(GameError::AlreadyThrown);
+        }
+        let commitment = Commitment {
+            hash: hash.clone().ok_or(GameError::NotCommitted)?,
+            hand: reveal.hand,
+            nonce: reveal.nonce,
+        };
+        if !commitment.verify() {
+            return Err(GameError::BadCommitment);
+        }
+        *revealed = Some(commitment);
+        Ok(())
+    }
+
+    pub fn status(&self, now: u64) -> Option<RemoteStatus> {
+        let remote = self.open.as_ref()?;
+        let committed1 = !remote.human1 | self.hashes.0.is_some();
+        let committed2 = !remote.human2 | self.hashes.1.is_some();
+        let revealed1 = !remote.human1 | self.reveals.0.is_some();
+        let revealed2 = !remote.human2 | self.reveals.1.is_some();
+        let over = self.deadline().is_some_and(|d| now > d);
+        let (late1, late2) = (over & !committed1, over & !committed2);
+        Some(RemoteStatus {
+            game_id: remote.game_id,
+            committed1,
+            committed2,
+            revealed1,
+            revealed2,
+            late1,
+            late2,
+            countdown_left: self.deadline().map(|d| d.saturating_sub(now)),
+            commitments: ((revealed1 | late1) & (revealed2 | late2)).then(|| self.reveals.clone()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameMode;
+    use crate::commit::hash;
+    use crate::countdown::LatePenalty;
+    use crate::strategy::StrategyKind;
+
+    #[test]
+    fn throw_box() {
+        let mut game = Game::with_seed(1);
+        let _ = game.set_mode(GameMode::RPS);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_bot("Robo", StrategyKind::Random);
+        let remote = RemoteMatch::new(&game, (1, 2, 1)).unwrap();
+        assert!(remote.human1 & remote.human2);
+        assert_eq!(remote.hands.len(), 3);
+
+        let mut throws = ThrowBox::new("s3cret");
+        assert!(throws.status(0).is_none());
+        let tokens = |t1: &str, t2: &str| (t1.to_string(), t2.to_string());
+        // Only the operator opens matches
+        let open = |remote| OpenMatch {remote, tokens: tokens("ta", "tb")};
+        assert_eq!(throws.open("guess", open(remote.clone())), Err(GameError::NotOperator));
+        assert!(throws.get_open().is_none());
+        assert!(throws.open("s3cret", open(remote)).is_ok());
+        let commit = |pid, token: &str, hand, nonce| Commit {game_id: (1, 2, 1), pid, token: token.to_string(), hash: hash(hand, nonce)};
+        let reveal = |pid, token: &str, hand, nonce: &str| Reveal {game_id: (1, 2, 1), pid, token: token.to_string(), hand, nonce: nonce.to_string()};
+        assert_eq!(throws.commit(commit(3, "tb", Rpssl::Rock, "a"), 0), Err(GameError::NoSuchPlayer));
+        assert_eq!(throws.commit(Commit {game_id: (1, 3, 1), ..commit(1, "ta", Rpssl::Rock, "a")}, 0), Err(GameError::NoSuchGame));
+        // Alice can't throw for Bob without his link
+        assert_eq!(throws.commit(commit(2, "ta", Rpssl::Rock, "x"), 0), Err(GameError::BadToken));
+        assert_eq!(throws.commit(commit(2, "", Rpssl::Rock, "x"), 0), Err(GameError::BadToken));
+        assert!(throws.commit(commit(2, "tb", Rpssl::Paper, "b"), 0).is_ok());
+        assert_eq!(throws.commit(commit(2, "tb", Rpssl::Rock, "b"), 0), Err(GameError::AlreadyThrown));
+        // Bob can't reveal before Alice has committed
+        assert_eq!(throws.reveal(reveal(2, "tb", Rpssl::Paper, "b"), 0), Err(GameError::NotCommitted));
+        let status = throws.status(0).unwrap();
+        assert!(!status.committed1 & status.committed2);
+        assert!(throws.commit(commit(1, "ta", Rpssl::Rock, "a"), 0).is_ok());
+        assert_eq!(throws.reveal(reveal(2, "ta", Rpssl::Paper, "b"), 0), Err(GameError::BadToken));
+        assert_eq!(throws.reveal(reveal(2, "tb", Rpssl::Vampire, "b"), 0), Err(GameError::InvalidHand));
+        assert_eq!(throws.reveal(reveal(2, "tb", Rpssl::Scissors, "b"), 0), Err(GameError::BadCommitment));
+        assert!(throws.reveal(reveal(2, "tb", Rpssl::Paper, "b"), 0).is_ok());
+        // Bob's hand stays hidden until Alice has revealed
+        assert_eq!(throws.status(0).unwrap().hands(), None);
+        assert!(throws.reveal(reveal(1, "ta", Rpssl::Rock, "a"), 0).is_ok());
+        let status = throws.status(0).unwrap();
+        assert_eq!(status.hands(), Some((Rpssl::Rock, Rpssl::Paper)));
+        let (c1, c2) = status.commitments.unwrap();
+        assert!(game.add_committed_result((1, 2, 1), c1, c2).is_ok());
+
+        // Only the human side of a bot match throws
+        let remote = RemoteMatch::new(&game, (1, 3, 1)).unwrap();
+        assert!(throws.open("s3cret", OpenMatch {remote, tokens: tokens("tc", "")}).is_ok());
+        let commit = |pid, token: &str, hand, nonce| Commit {game_id: (1, 3, 1), pid, token: token.to_string(), hash: hash(hand, nonce)};
+        assert_eq!(throws.commit(commit(3, "", Rpssl::Rock, "c"), 0), Err(GameError::NoSuchPlayer));
+        // Tokens of the previous match are no longer valid
+        assert_eq!(throws.commit(commit(1, "ta", Rpssl::Rock, "c"), 0), Err(GameError::BadToken));
+        assert!(throws.commit(commit(1, "tc", Rpssl::Rock, "c"), 0).is_ok());
+        assert!(throws.reveal(Reveal {game_id: (1, 3, 1), pid: 1, token: "tc".to_string(), hand: Rpssl::Rock, nonce: "c".to_string()}, 0).is_ok());
+        assert_eq!(throws.status(0).unwrap().hands(), Some((Rpssl::Rock, Rpssl::None)));
+        assert_eq!(throws.close("tc", (1, 3, 1)), Err(GameError::NotOperator));
+        assert!(throws.get_open().is_some());
+        assert!(throws.close("s3cret", (1, 3, 1)).is_ok());
+        assert!(throws.get_open().is_none());
+
+        // Bob misses the end of the countdown and Charlie doesn't have to wait
+        let _ = game.add_player("Charlie");
+        game.set_countdown(Some(Countdown {beat_ms: 500, penalty: LatePenalty::Loss}));
+        let remote = RemoteMatch::new(&game, (2, 4, 1)).unwrap();
+        assert!(throws.open("s3cret", OpenMatch {remote, tokens: tokens("tb", "td")}).is_ok());
+        assert_eq!(throws.status(0).unwrap().countdown_left, None);
+        assert_eq!(throws.start("s3cret", (1, 3, 1), 1000), Err(GameError::NoSuchGame));
+        assert_eq!(throws.start("td", (2, 4, 1), 1000), Err(GameError::NotOperator));
+        assert!(throws.start("s3cret", (2, 4, 1), 1000).is_ok());
+        assert_eq!(throws.status(1200).unwrap().countdown_left, Some(1300));
+        let commit = |pid, token: &str, hand, nonce| Commit {game_id: (2, 4, 1), pid, token: token.to_string(), hash: hash(hand, nonce)};
+        assert!(throws.commit(commit(4, "td", Rpssl::Paper, "d"), 2000).is_ok());
+        assert_eq!(throws.commit(commit(2, "tb", Rpssl::Rock, "e"), 2600), Err(GameError::TooLate));
+        let status = throws.status(2600).unwrap();
+        assert!(status.late1 & !status.late2);
+        assert_eq!(status.countdown_left, Some(0));
+        assert!(throws.reveal(Reveal {game_id: (2, 4, 1), pid: 4, token: "td".to_string(), hand: Rpssl::Paper, nonce: "d".to_string()}, 2700).is_ok());
+        let (c1, c2) = throws.status(2700).unwrap().commitments.unwrap();
+        assert!(c1.is_none());
+        assert!(game.add_committed_result((2, 4, 1), c1, c2).is_ok());
+        assert_eq!(game.get_match((2, 4, 1)).unwrap().play1, Rpssl::Rock);
+
+        assert_eq!(RemoteMatch::new(&game, (1, 2, 1)), Err(GameError::AlreadyPlayed));
+
+        // A server without a secret can't be operated at all
+        let mut throws = ThrowBox::new("");
+        let remote = RemoteMatch::new(&game, (3, 4, 1)).unwrap();
+        assert_eq!(throws.open("", OpenMatch {remote, tokens: tokens("", "td")}), Err(GameError::NotOperator));
+    }
+}
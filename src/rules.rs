@@ -0,0 +1,118 @@
+use crate::{GameMode, Playable, RpsResult, Rpssl};
+
+// One pair of the beats relation, "Scissors cuts Paper"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Beat {
+    pub winner: Rpssl,
+    pub loser: Rpssl,
+    pub verb: &'static str,
+}
+
+impl Beat {
+    pub fn sentence(&self) -> String {
+        format!("{} {} {}", self.winner.name(), self.verb, self.loser.name())
+    }
+
+    // Message catalogue key of the sentence, e.g. "beats.scissors.paper"
+    pub fn key(&self) -> String {
+        format!("beats.{}.{}", self.winner.name().to_lowercase(), self.loser.name().to_lowercase())
+    }
+
+    // Message catalogue key of the verb alone, e.g. "verbs.scissors.paper"
+    pub fn verb_key(&self) -> String {
+        format!("verbs.{}.{}", self.winner.name().to_lowercase(), self.loser.name().to_lowercase())
+    }
+}
+
+// Everything that decides a match in a game mode. Built from the same
+// functions that score the matches, so descriptions can't go stale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    pub game_mode: GameMode,
+    pub hands: Vec<Rpssl>,
+    pub beats: Vec<Beat>,
+    pub win_points: u16,
+    pub draw_points: u16,
+    pub loss_points: u16,
+    // Points for the hand played, regardless of the result
+    pub hand_points: Vec<(Rpssl, u16)>,
+}
+
+fn verb(winner: Rpssl, loser: Rpssl) -> &'static str {
+    match (winner, loser) {
+        (Rpssl::Scissors, Rpssl::Paper) => "cuts",
+        (Rpssl::Paper, Rpssl::Rock) => "covers",
+        (Rpssl::Rock, Rpssl::Lizard) | (Rpssl::Rock, Rpssl::Scissors) => "crushes",
+        (Rpssl::Lizard, Rpssl::Vampire) => "poisons",
+        (Rpssl::Vampire, Rpssl::Scissors) => "smashes",
+        (Rpssl::Scissors, Rpssl::Lizard) => "decapitates",
+        (Rpssl::Lizard, Rpssl::Paper) => "eats",
+        (Rpssl::Paper, Rpssl::Vampire) => "disproves",
+        (Rpssl::Vampire, Rpssl::Rock) => "vaporizes",
+        _ => "beats",
+    }
+}
+
+impl GameMode {
+    pub fn rules(&self) -> Rules {
+        let hands = self.hands();
+        let beats = hands.iter()
+            .flat_map(|w| hands.iter()
+                .filter(|l| w.result(l) == RpsResult::Win)
+                .map(|l| Beat {winner: *w, loser: *l, verb: verb(*w, *l)}))
+            .collect();
+        let mut hand_points = hands.iter().map(|h| (*h, h.get_score())).collect::<Vec<_>>();
+        hand_points.sort_by_key(|(_, points)| std::cmp::Reverse(*points));
+        Rules {
+            game_mode: *self,
+            hands,
+            beats,
+            win_points: RpsResult::Win.get_score(*self),
+            draw_points: RpsResult::Draw.get_score(*self),
+            loss_points: RpsResult::Lose.get_score(*self),
+            hand_points,
+        }
+    }
+}
+
+impl Rules {
+    // The rule deciding a match between two hands, None for a draw
+    pub fn beat(&self, hand: Rpssl, other: Rpssl) -> Option<&Beat> {
+        self.beats.iter().find(|b| ((b.winner == hand) & (b.loser == other)) | ((b.winner == other) & (b.loser == hand)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules() {
+        for mode in [GameMode::RPS, GameMode::RPSSL] {
+            let rules = mode.rules();
+            let n = rules.hands.len();
+            // Every pair of different hands is decided by exactly one rule
+            assert_eq!(rules.beats.len(), n * (n - 1) / 2);
+            for a in &rules.hands {
+                for b in &rules.hands {
+                    match rules.beat(*a, *b) {
+                        Some(beat) => {
+                            assert_eq!(beat.winner.result(&beat.loser), RpsResult::Win);
+                            assert_ne!(beat.verb, "beats");
+                        },
+                        None => assert_eq!(a, b),
+                    }
+                }
+            }
+        }
+
+        let rpssl = GameMode::RPSSL.rules();
+        assert_eq!((rpssl.win_points, rpssl.draw_points, rpssl.loss_points), (10, 5, 0));
+        assert_eq!(rpssl.hand_points[0], (Rpssl::Vampire, 5));
+        let beat = rpssl.beat(Rpssl::Paper, Rpssl::Scissors).unwrap();
+        assert_eq!(beat.sentence(), "Scissors cuts Paper");
+        assert_eq!(beat.key(), "beats.scissors.paper");
+        assert_eq!(beat.verb_key(), "verbs.scissors.paper");
+        assert_eq!(GameMode::RPS.rules().win_points, 6);
+    }
+}
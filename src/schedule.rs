@@ -0,0 +1,140 @@
+use crate::{Action, Game, Match};
+use crate::error::GameError;
+
+// Queue priorities are split in tiers so that manual changes always win
+// over the computed order. Played matches stay negative
+static PINNED: i64 = 2_000_000;
+static NORMAL: i64 = 1_000_000;
+
+impl Game {
+    // Queue priority of an unplayed match, given its computed priority
+    pub(crate) fn scheduled_priority(&self, game_id: (u16, u16, u16), priority: i64) -> i64 {
+        if let Some(i) = self.pins.iter().position(|k| *k == game_id) {
+            return PINNED + i as i64;
+        }
+        let priority = priority.clamp(1, NORMAL - 1);
+        if self.deferred.contains(&game_id) | self.has_away_player(game_id) {
+            priority
+        } else {
+            NORMAL + priority
+        }
+    }
+
+    fn has_away_player(&self, game_id: (u16, u16, u16)) -> bool {
+        [game_id.0, game_id.1].iter()
+            .any(|pid| self.player_list.get(pid).is_some_and(|p| p.away))
+    }
+
+    fn check_unplayed(&self, game_id: (u16, u16, u16)) -> Result<(), GameError> {
+        match self.match_list.get(&game_id) {
+            None => Err(GameError::NoSuchGame),
+            Some(m) if m.result.is_some() => Err(GameError::AlreadyPlayed),
+            Some(_) => Ok(()),
+        }
+    }
+
+    // Moves a match to the front of the queue
+    pub fn pin_match(&mut self, game_id: (u16, u16, u16)) -> Result<(), GameError> {
+        self.check_unplayed(game_id)?;
+        self.deferred.remove(&game_id);
+        self.pins.retain(|k| *k != game_id);
+        self.pins.push(game_id);
+        self.actions.push(Action::PinMatch(game_id));
+        self.update_priorities();
+        Ok(())
+    }
+
+    // Moves a match to the back of the queue
+    pub fn push_back(&mut self, game_id: (u16, u16, u16)) -> Result<(), GameError> {
+        self.check_unplayed(game_id)?;
+        self.pins.retain(|k| *k != game_id);
+        self.deferred.insert(game_id);
+        self.actions.push(Action::PushBack(game_id));
+        self.update_priorities();
+        Ok(())
+    }
+
+    pub fn set_away(&mut self, pid: u16, away: bool) -> Result<(), GameError> {
+        let player = self.player_list.get_mut(&pid).ok_or(GameError::NoSuchPlayer)?;
+        player.away = away;
+        self.actions.push(Action::SetAway(pid, away));
+        self.update_priorities();
+        Ok(())
+    }
+
+    pub fn is_pinned(&self, game_id: (u16, u16, u16)) -> bool {
+        self.pins.contains(&game_id)
+    }
+
+    pub fn is_deferred(&self, game_id: (u16, u16, u16)) -> bool {
+        self.deferred.contains(&game_id)
+    }
+
+    // All unplayed matches in the order they will be played
+    pub fn get_schedule(&self) -> Vec<&Match> {
+        self.queue.clone().into_sorted_iter()
+            .filter(|(_k, prior)| *prior >= 0)
+            .map(|(k, _p)| self.match_list.get(&k).expect("Match list should include all queue elements"))
+            .collect()
+    }
+
+    // Scheduled matches that can be played now, nobody is away
+    pub fn get_available_games(&self) -> Vec<&Match> {
+        self.get_schedule().into_iter()
+            .filter(|m| !self.has_away_player((m.player1, m.player2, m.round)))
+            .collect()
+    }
+
+    // Match after the next one
+    pub fn get_on_deck(&self) -> Option<&Match> {
+        self.get_available_games().into_iter().nth(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Game, Rpssl};
+
+    fn ids(game: &Game) -> Vec<(u16, u16, u16)> {
+        game.get_schedule().iter().map(|m| (m.player1, m.player2, m.round)).collect()
+    }
+
+    #[test]
+    fn schedule() {
+        let mut game = Game::with_seed(1);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_player("Charlie");
+        let _ = game.add_player("David");
+        assert_eq!(ids(&game).len(), 6);
+
+        let last = *ids(&game).last().unwrap();
+        assert!(game.pin_match(last).is_ok());
+        assert_eq!(ids(&game)[0], last);
+        assert_eq!(game.get_next_game().map(|m| (m.player1, m.player2, m.round)), Some(last));
+
+        let first = ids(&game)[1];
+        assert!(game.push_back(first).is_ok());
+        assert_eq!(*ids(&game).last().unwrap(), first);
+        assert!(game.is_deferred(first));
+
+        // Alice is away, only Bob, Charlie and David can play
+        assert!(game.set_away(1, true).is_ok());
+        let available = game.get_available_games();
+        assert!(available.iter().all(|m| (m.player1 != 1) & (m.player2 != 1)));
+        assert_eq!(available.len(), 3);
+        assert!(game.get_on_deck().is_some());
+        let next = game.get_next_game().map(|m| (m.player1, m.player2, m.round)).unwrap();
+        game.add_result(next, Rpssl::Rock, Rpssl::Paper);
+        assert!(!game.is_pinned(next));
+        assert!(game.pin_match(next).is_err());
+        assert_eq!(game.get_left_n(), 5);
+
+        let replayed = Game::from_saved(&game.save()).unwrap();
+        assert_eq!(ids(&replayed), ids(&game));
+        assert!(replayed.get_player(1).unwrap().away);
+
+        assert!(game.set_away(1, false).is_ok());
+        assert_eq!(game.get_available_games().len(), 5);
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+use crate::{Game, SavedGame};
+use crate::error::GameError;
+use crate::strategy::StrategyKind;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeasonPlayer {
+    pub id: u16,
+    pub name: String,
+    pub bot: Option<StrategyKind>,
+}
+
+// One tournament of the season. Player ids of the game are mapped to
+// season ids so that players keep their identity across tournaments
+#[derive(Debug, Clone)]
+pub struct Tournament {
+    pub game: Game,
+    pub players: BTreeMap<u16, u16>,
+}
+
+impl Tournament {
+    // Places only count once every match has been played
+    pub fn is_finished(&self) -> bool {
+        (self.game.get_played_n() > 0) & (self.game.get_left_n() == 0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedTournament {
+    pub game: SavedGame,
+    pub players: BTreeMap<u16, u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSeason {
+    pub name: String,
+    pub roster: Vec<SeasonPlayer>,
+    pub points: Vec<u16>,
+    pub tournaments: Vec<SavedTournament>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeasonStanding {
+    pub id: u16,
+    pub points: u16,
+    pub attended: usize,
+    pub tournament_wins: usize,
+    // Best finishing position, None if not attended
+    pub best: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Season {
+    pub name: String,
+    pub roster: BTreeMap<u16, SeasonPlayer>,
+    // Season points for 1st, 2nd, 3rd... place. Places beyond the list get nothing
+    pub points: Vec<u16>,
+    pub tournaments: Vec<Tournament>,
+}
+
+impl Season {
+    pub fn new(name: &str) -> Season {
+        Season {
+            name: name.to_string(),
+            roster: BTreeMap::new(),
+            points: vec![10, 8, 6, 5, 4, 3, 2, 1],
+            tournaments: Vec::new(),
+        }
+    }
+
+    pub fn set_points(&mut self, points: Vec<u16>) {
+        self.points = points;
+    }
+
+    pub fn add_player(&mut self, name: &str, bot: Option<StrategyKind>) -> Result<u16, GameError> {
+        if self.roster.values().any(|p| p.name == name) {
+            return Err(GameError::PlayerExists(name.to_string()));
+        }
+        let id = self.roster.keys().max().unwrap_or(&0) + 1;
+        self.roster.insert(id, SeasonPlayer {id, name: name.to_string(), bot});
+        Ok(id)
+    }
+
+    pub fn get_player_id(&self, name: &str) -> Option<u16> {
+        self.roster.values().find(|p| p.name == name).map(|p| p.id)
+    }
+
+    // Links the game's players to the roster by name. Players that are not
+    // in the roster yet are added to it
+    fn map_players(&mut self, game: &Game) -> BTreeMap<u16, u16> {
+        let mut players = BTreeMap::new();
+        for p in game.player_list.values() {
+            let id = match self.get_player_id(&p.name) {
+                Some(id) => id,
+                None => self.add_player(&p.name, p.bot).expect("Name should be new"),
+            };
+            players.insert(p.id, id);
+        }
+        players
+    }
+
+    pub fn add_tournament(&mut self, game: Game) -> usize {
+        let players = self.map_players(&game);
+        self.tournaments.push(Tournament {game, players});
+        self.tournaments.len() - 1
+    }
+
+    // Tournament played with the given seed. Seeds are drawn at random,
+    // so they identify the games of the season
+    pub fn find_tournament(&self, seed: u64) -> Option<usize> {
+        self.tournaments.iter().position(|t| t.game.get_seed() == seed)
+    }
+
+    // Replaces a tournament with a newer state of the same game
+    pub fn update_tournament(&mut self, index: usize, game: Game) -> Result<(), GameError> {
+        if index >= self.tournaments.len() {
+            return Err(GameError::NoSuchTournament);
+        }
+        let players = self.map_players(&game);
+        self.tournaments[index] = Tournament {game, players};
+        Ok(())
+    }
+
+    // Starts a new tournament with the given roster players. Game mode,
+    // rounds, tiebreakers and custom quotes are taken from the previous tournament
+    pub fn new_tournament(&mut self, attendees: &[u16]) -> Result<usize, GameError> {
+        let mut game = Game::new();
+        if let Some(previous) = self.tournaments.last() {
+            game.set_mode(previous.game.get_mode())?;
+            game.set_rounds(previous.game.get_rounds());
+            game.set_tiebreakers(previous.game.get_tiebreakers());
+            for quote in previous.game.get_custom_quotes() {
+                game.add_quote(quote.clone())?;
+            }
+        }
+        for id in attendees {
+            let player = self.roster.get(id).ok_or(GameError::NoSuchPlayer)?;
+            match player.bot {
+                Some(kind) => game.add_bot(&player.name, kind)?,
+                None => game.add_player(&player.name)?,
+            }
+        }
+        Ok(self.add_tournament(game))
+    }
+
+    // Roster ids of the players that took part in the tournament
+    pub fn get_attendees(&self, index: usize) -> Vec<u16> {
+        self.tournaments.get(index)
+            .map(|t| t.players.values().copied().collect())
+            .unwrap_or_default()
+    }
+
+    // Whether the player attended each tournament, in order
+    pub fn get_attendance(&self, id: u16) -> Vec<bool> {
+        self.tournaments.iter()
+            .map(|t| t.players.values().any(|p| *p == id))
+            .collect()
+    }
+
+    // Finishing positions of roster players in one tournament. Players
+    // sharing a rank get the points of that rank. Unfinished tournaments
+    // have no results yet
+    pub fn get_results(&self, index: usize) -> Vec<(u16, usize, u16)> {
+        let Some(t) = self.tournaments.get(index).filter(|t| t.is_finished()) else {
+            return vec![];
+        };
+        t.game.get_standings().iter()
+            .filter_map(|s| {
+                let id = *t.players.get(&s.pid)?;
+                let points = self.points.get(s.rank - 1).copied().unwrap_or(0);
+                Some((id, s.rank, points))
+            })
+            .collect()
+    }
+
+    pub fn get_standings(&self) -> Vec<SeasonStanding> {
+        let mut standings = self.roster.keys().map(|&id| SeasonStanding {
+            id,
+            points: 0,
+            attended: 0,
+            tournament_wins: 0,
+            best: None,
+        }).collect::<Vec<_>>();
+        // Unfinished tournaments count as attended but give no points
+        for t in &self.tournaments {
+            for id in t.players.values() {
+                if let Some(s) = standings.iter_mut().find(|s| s.id == *id) {
+                    s.attended += 1;
+                }
+            }
+        }
+        for index in 0..self.tournaments.len() {
+            for (id, rank, points) in self.get_results(index) {
+                let Some(s) = standings.iter_mut().find(|s| s.id == id) else {
+                    continue;
+                };
+                s.points += points;
+                if rank == 1 {
+                    s.tournament_wins += 1;
+                }
+                s.best = Some(s.best.map_or(rank, |b| b.min(rank)));
+            }
+        }
+        standings.sort_by_key(|s| (std::cmp::Reverse(s.points), std::cmp::Reverse(s.tournament_wins), s.id));
+        standings
+    }
+
+    pub fn save(&self) -> SavedSeason {
+        SavedSeason {
+            name: self.name.clone(),
+            roster: self.roster.values().cloned().collect(),
+            points: self.points.clone(),
+            tournaments: self.tournaments.iter().map(|t| SavedTournament {
+                game: t.game.save(),
+                players: t.players.clone(),
+            }).collect(),
+        }
+    }
+
+    pub fn from_saved(saved: &SavedSeason) -> Result<Season, GameError> {
+        let tournaments = saved.tournaments.iter().map(|t| {
+            Ok(Tournament {game: Game::from_saved(&t.game)?, players: t.players.clone()})
+        }).collect::<Result<Vec<_>, GameError>>()?;
+        Ok(Season {
+            name: saved.name.clone(),
+            roster: saved.roster.iter().map(|p| (p.id, p.clone())).collect(),
+            points: saved.points.clone(),
+            tournaments,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameMode, Rpssl};
+
+    #[test]
+    fn season() {
+        let mut season = Season::new("Syksy");
+        let alice = season.add_player("Alice", None).unwrap();
+        let bob = season.add_player("Bob", None).unwrap();
+        let charlie = season.add_player("Charlie", None).unwrap();
+        assert!(season.add_player("Bob", None).is_err());
+
+        let first = season.new_tournament(&[alice, bob, charlie]).unwrap();
+        let mut game = season.tournaments[first].game.clone();
+        let _ = game.set_mode(GameMode::RPSSL);
+        game.add_result((1, 2, 1), Rpssl::Scissors, Rpssl::Rock);
+        game.add_result((1, 3, 1), Rpssl::Rock, Rpssl::Scissors);
+        game.add_result((2, 3, 1), Rpssl::Rock, Rpssl::Scissors);
+        season.update_tournament(first, game).unwrap();
+
+        // Charlie is away, David joins for the second tournament
+        let second = season.new_tournament(&[bob, alice]).unwrap();
+        assert_eq!(season.tournaments[second].game.get_mode(), GameMode::RPSSL);
+        let mut game = season.tournaments[second].game.clone();
+        let _ = game.add_player("David");
+        // Bob is player 1 and Alice player 2 in the second tournament
+        game.add_result((1, 2, 1), Rpssl::Vampire, Rpssl::Rock);
+        season.update_tournament(second, game).unwrap();
+        let david = season.get_player_id("David").unwrap();
+        // Only 1 of the 3 matches is played
+        assert!(!season.tournaments[second].is_finished());
+        assert_eq!(season.get_results(second), vec![]);
+
+        assert_eq!(season.get_attendance(charlie), vec![true, false]);
+        assert_eq!(season.get_attendance(david), vec![false, true]);
+        assert_eq!(season.get_attendees(second).len(), 3);
+        let seed = season.tournaments[second].game.get_seed();
+        assert_eq!(season.find_tournament(seed), Some(second));
+
+        let standings = season.get_standings();
+        let bob_standing = standings.iter().find(|s| s.id == bob).unwrap();
+        assert_eq!(bob_standing.attended, 2);
+        assert_eq!(bob_standing.tournament_wins, 1);
+        assert_eq!(bob_standing.best, Some(1));
+        assert_eq!(bob_standing.points, 10);
+        assert_eq!(standings[0].id, bob);
+        assert_eq!(standings[1].id, alice);
+        assert_eq!(standings[1].points, 8);
+        let david_standing = standings.iter().find(|s| s.id == david).unwrap();
+        assert_eq!((david_standing.attended, david_standing.points, david_standing.best), (1, 0, None));
+
+        let restored = Season::from_saved(&season.save()).unwrap();
+        assert_eq!(restored.save(), season.save());
+        assert_eq!(restored.get_standings(), standings);
+
+        // A new tournament without results gives nothing yet
+        let third = season.new_tournament(&[alice, bob, charlie]).unwrap();
+        assert_eq!(season.get_results(third), vec![]);
+        let after = season.get_standings();
+        assert_eq!(after.iter().map(|s| s.points).collect::<Vec<_>>(), standings.iter().map(|s| s.points).collect::<Vec<_>>());
+        assert_eq!(after.iter().find(|s| s.id == alice).unwrap().attended, 3);
+        assert_eq!(after.iter().find(|s| s.id == charlie).unwrap().tournament_wins, 0);
+    }
+}
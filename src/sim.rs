@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+use serde::Serialize;
+use crate::{Game, GameMode, RpsResult};
+use crate::error::GameError;
+use crate::strategy::StrategyKind;
+
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    pub tournaments: usize,
+    pub rounds: usize,
+    pub game_mode: GameMode,
+    // Tournament i is played with seed + i
+    pub seed: u64,
+    pub bots: Vec<StrategyKind>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BotSummary {
+    pub name: String,
+    // positions[i] is how many times the bot finished at place i + 1
+    pub positions: Vec<usize>,
+    pub mean_score: f64,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PairSummary {
+    pub bot: String,
+    pub opponent: String,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl PairSummary {
+    pub fn win_rate(&self) -> f64 {
+        let total = self.wins + self.draws + self.losses;
+        if total == 0 {
+            0.0
+        } else {
+            self.wins as f64 / total as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimReport {
+    pub tournaments: usize,
+    pub rounds: usize,
+    pub game_mode: GameMode,
+    pub seed: u64,
+    pub bots: Vec<BotSummary>,
+    pub head_to_head: Vec<PairSummary>,
+}
+
+// Bots are named after their strategy, numbered if the same one is used twice
+fn bot_names(bots: &[StrategyKind]) -> Vec<String> {
+    bots.iter().enumerate().map(|(i, kind)| {
+        if bots.iter().filter(|k| *k == kind).count() > 1 {
+            format!("{} {}", kind.str(), i + 1)
+        } else {
+            kind.str().to_string()
+        }
+    }).collect()
+}
+
+pub fn play_tournament(config: &SimConfig, seed: u64) -> Result<Game, GameError> {
+    let mut game = Game::with_seed(seed);
+    game.set_mode(config.game_mode)?;
+    game.set_rounds(config.rounds);
+    for (name, kind) in bot_names(&config.bots).iter().zip(&config.bots) {
+        game.add_bot(name, *kind)?;
+    }
+    while let Some(m) = game.get_next_game() {
+        let game_id = (m.player1, m.player2, m.round);
+        game.add_result_with_bots(game_id, crate::Rpssl::None, crate::Rpssl::None);
+    }
+    Ok(game)
+}
+
+pub fn run(config: &SimConfig) -> Result<SimReport, GameError> {
+    if config.bots.len() < 2 {
+        return Err(GameError::TooFewBots);
+    }
+    let names = bot_names(&config.bots);
+    let n = names.len();
+    let mut bots = names.iter().map(|name| BotSummary {
+        name: name.clone(),
+        positions: vec![0; n],
+        ..Default::default()
+    }).collect::<Vec<_>>();
+    let mut pairs: BTreeMap<(u16, u16), PairSummary> = BTreeMap::new();
+    let mut total_scores = vec![0u64; n];
+
+    for i in 0..config.tournaments {
+        let mut game = play_tournament(config, config.seed.wrapping_add(i as u64))?;
+        for (place, player) in game.get_scores().iter().enumerate() {
+            let idx = (player.id - 1) as usize;
+            bots[idx].positions[place] += 1;
+            total_scores[idx] += player.score as u64;
+        }
+        for m in game.match_list.values() {
+            for (pid, opponent) in [(m.player1, m.player2), (m.player2, m.player1)] {
+                let pair = pairs.entry((pid, opponent)).or_insert_with(|| PairSummary {
+                    bot: names[(pid - 1) as usize].clone(),
+                    opponent: names[(opponent - 1) as usize].clone(),
+                    ..Default::default()
+                });
+                let bot = &mut bots[(pid - 1) as usize];
+                match m.get_result_for_player(pid) {
+                    Some(RpsResult::Win) => {
+                        pair.wins += 1;
+                        bot.wins += 1;
+                    },
+                    Some(RpsResult::Draw) => {
+                        pair.draws += 1;
+                        bot.draws += 1;
+                    },
+                    Some(RpsResult::Lose) => {
+                        pair.losses += 1;
+                        bot.losses += 1;
+                    },
+                    None => (),
+                }
+            }
+        }
+    }
+    for (bot, total) in bots.iter_mut().zip(total_scores) {
+        bot.mean_score = total as f64 / config.tournaments.max(1) as f64;
+    }
+
+    Ok(SimReport {
+        tournaments: config.tournaments,
+        rounds: config.rounds,
+        game_mode: config.game_mode,
+        seed: config.seed,
+        bots,
+        head_to_head: pairs.into_values().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn simulate() {
+        let config = SimConfig {
+            tournaments: 20,
+            rounds: 2,
+            game_mode: GameMode::RPSSL,
+            seed: 5,
+            bots: StrategyKind::iter().collect(),
+        };
+        let report = run(&config).unwrap();
+        assert_eq!(report.bots.len(), 5);
+        assert_eq!(report.head_to_head.len(), 20);
+        for bot in &report.bots {
+            assert_eq!(bot.positions.iter().sum::<usize>(), 20);
+            // Every bot plays 4 opponents twice per tournament
+            assert_eq!(bot.wins + bot.draws + bot.losses, 20 * 8);
+        }
+        for place in 0..5 {
+            assert_eq!(report.bots.iter().map(|b| b.positions[place]).sum::<usize>(), 20);
+        }
+
+        // Same seed, same outcome
+        let again = run(&config).unwrap();
+        assert_eq!(
+            report.bots.iter().map(|b| b.positions.clone()).collect::<Vec<_>>(),
+            again.bots.iter().map(|b| b.positions.clone()).collect::<Vec<_>>(),
+        );
+
+        let config = SimConfig {bots: vec![StrategyKind::Random, StrategyKind::Random], ..config};
+        let report = run(&config).unwrap();
+        assert_eq!(report.bots[0].name, "Random 1");
+        assert!(run(&SimConfig {bots: vec![StrategyKind::Random], ..config}).is_err());
+    }
+}
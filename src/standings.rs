@@ -0,0 +1,292 @@
+use std::collections::{BTreeMap, BTreeSet};
+use serde::{Serialize, Deserialize};
+use strum_macros::EnumIter;
+use crate::{Game, RpsResult};
+
+// Ways to order players with equal scores, applied in the configured order
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter, Serialize, Deserialize)]
+pub enum Tiebreaker {
+    // Points from the matches between the tied players only
+    HeadToHead,
+    Wins,
+    // Points from results, as opposed to hand bonus points
+    ResultPoints,
+    // Scores of beaten opponents plus half the scores of drawn opponents
+    SonnebornBerger,
+    FewestPlayed,
+}
+
+impl Tiebreaker {
+    pub fn str(&self) -> &str {
+        match self {
+            Self::HeadToHead => "Head-to-head",
+            Self::Wins => "Wins",
+            Self::ResultPoints => "Result points",
+            Self::SonnebornBerger => "Sonneborn-Berger",
+            Self::FewestPlayed => "Fewest played",
+        }
+    }
+
+    pub fn default_chain() -> Vec<Tiebreaker> {
+        vec![
+            Self::HeadToHead,
+            Self::Wins,
+            Self::ResultPoints,
+            Self::SonnebornBerger,
+            Self::FewestPlayed,
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub pid: u16,
+    // Players with a shared rank have the same rank
+    pub rank: usize,
+    pub shared: bool,
+    pub score: u16,
+    // Tiebreaker that put this player below the previous one, if they
+    // had the same score
+    pub decided_by: Option<Tiebreaker>,
+    // Tiebreak values that were compared for this player
+    pub tiebreaks: Vec<(Tiebreaker, f64)>,
+}
+
+impl Standing {
+    // Rank as shown in the standings, "=2." for shared ranks
+    pub fn rank_str(&self) -> String {
+        if self.shared {
+            format!("={}.", self.rank)
+        } else {
+            format!("{}.", self.rank)
+        }
+    }
+}
+
+// Why a win was a surprise, judged by the standings before the match
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Surprise {
+    // Winner had fewer points than the loser
+    Upset,
+    // Same, and the loser was ranked first
+    LeaderBeaten,
+}
+
+// Score and rank of every player after each played match, in play order.
+// Index 0 is the situation before the first match
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StandingsHistory {
+    pub players: Vec<u16>,
+    pub matches: Vec<(u16, u16, u16)>,
+    pub scores: BTreeMap<u16, Vec<u16>>,
+    pub ranks: BTreeMap<u16, Vec<usize>>,
+}
+
+impl StandingsHistory {
+    // Number of points in each series
+    pub fn steps(&self) -> usize {
+        self.matches.len() + 1
+    }
+
+    // Surprise in the win of the match played at step. The standings
+    // before it are at step and after it at step + 1. Nobody is surprised
+    // by a win over an equal
+    pub fn surprise(&self, winner: u16, loser: u16, step: usize) -> Option<Surprise> {
+        let score = |pid: u16| self.scores.get(&pid).and_then(|s| s.get(step)).copied();
+        let rank = |pid: u16| self.ranks.get(&pid).and_then(|r| r.get(step)).copied();
+        match (score(winner)?, score(loser)?) {
+            (w, l) if w >= l => None,
+            _ if rank(loser) == Some(1) => Some(Surprise::LeaderBeaten),
+            _ => Some(Surprise::Upset),
+        }
+    }
+
+    fn push(&mut self, standings: &[Standing]) {
+        for s in standings {
+            self.scores.entry(s.pid).or_default().push(s.score);
+            self.ranks.entry(s.pid).or_default().push(s.rank);
+        }
+    }
+}
+
+impl Game {
+    pub fn get_standings_history(&self) -> StandingsHistory {
+        let played = self.get_played_games().iter()
+            .map(|(m, _p)| (*m).clone())
+            .collect::<Vec<_>>();
+        // Replay the results one by one on a copy without results
+        let mut replay = self.clone();
+        for m in replay.match_list.values_mut() {
+            m.result = None;
+        }
+        let mut history = StandingsHistory {
+            players: self.player_list.keys().copied().collect(),
+            ..Default::default()
+        };
+        history.push(&replay.get_standings());
+        for m in played {
+            let game_id = (m.player1, m.player2, m.round);
+            replay.match_list.insert(game_id, m);
+            history.matches.push(game_id);
+            history.push(&replay.get_standings());
+        }
+        history
+    }
+
+    pub fn get_tiebreakers(&self) -> Vec<Tiebreaker> {
+        self.tiebreakers.clone()
+    }
+
+    fn tiebreak_value(&self, tiebreaker: Tiebreaker, pid: u16, group: &BTreeSet<u16>) -> f64 {
+        let game_mode = self.get_mode();
+        let matches = self.match_list.values()
+            .filter(|m| m.result.is_some() & ((m.player1 == pid) | (m.player2 == pid)));
+        match tiebreaker {
+            Tiebreaker::HeadToHead => matches
+                .filter(|m| group.contains(&m.player1) & group.contains(&m.player2))
+                .map(|m| m.get_score_for_player(pid, game_mode) as f64)
+                .sum(),
+            Tiebreaker::Wins => matches
+                .filter(|m| m.get_result_for_player(pid) == Some(RpsResult::Win))
+                .count() as f64,
+            Tiebreaker::ResultPoints => matches
+                .filter_map(|m| m.get_result_for_player(pid))
+                .map(|r| r.get_score(game_mode) as f64)
+                .sum(),
+            Tiebreaker::SonnebornBerger => matches
+                .map(|m| {
+                    let opponent = if m.player1 == pid { m.player2 } else { m.player1 };
+                    let opponent_score = self.get_score_for_player(opponent).1 as f64;
+                    match m.get_result_for_player(pid) {
+                        Some(RpsResult::Win) => opponent_score,
+                        Some(RpsResult::Draw) => opponent_score / 2.0,
+                        _ => 0.0,
+                    }
+                })
+                .sum(),
+            Tiebreaker::FewestPlayed => -(matches.count() as f64),
+        }
+    }
+
+    // Orders a group of tied players with the given tiebreakers. Returns
+    // the ordered players, each with the tiebreaker that separated it from
+    // the previous player and whether the tie stayed unbroken
+    fn break_ties(&self, group: Vec<u16>, tiebreakers: &[Tiebreaker], values: &mut Vec<(u16, Tiebreaker, f64)>) -> Vec<(u16, Option<Tiebreaker>, bool)> {
+        if group.len() == 1 {
+            return vec![(group[0], None, false)];
+        }
+        let Some((&tiebreaker, rest)) = tiebreakers.split_first() else {
+            return group.into_iter().map(|p| (p, None, true)).collect();
+        };
+        let members = group.iter().copied().collect::<BTreeSet<_>>();
+        let mut scored = group.iter()
+            .map(|&pid| (pid, self.tiebreak_value(tiebreaker, pid, &members)))
+            .collect::<Vec<_>>();
+        values.extend(scored.iter().map(|(pid, v)| (*pid, tiebreaker, *v)));
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut ordered = Vec::new();
+        let mut start = 0;
+        while start < scored.len() {
+            let end = start + scored[start..].iter().take_while(|(_, v)| *v == scored[start].1).count();
+            let subgroup = scored[start..end].iter().map(|(p, _)| *p).collect::<Vec<_>>();
+            let mut sub = self.break_ties(subgroup, rest, values);
+            if start > 0 {
+                sub[0].1 = Some(tiebreaker);
+            }
+            ordered.append(&mut sub);
+            start = end;
+        }
+        ordered
+    }
+
+    pub fn get_standings(&self) -> Vec<Standing> {
+        let mut scores = self.player_list.keys()
+            .map(|&pid| (pid, self.get_score_for_player(pid).1))
+            .collect::<Vec<_>>();
+        scores.sort_by_key(|(pid, score)| (std::cmp::Reverse(*score), *pid));
+
+        let mut standings: Vec<Standing> = Vec::new();
+        let mut start = 0;
+        while start < scores.len() {
+            let score = scores[start].1;
+            let end = start + scores[start..].iter().take_while(|(_, s)| *s == score).count();
+            let group = scores[start..end].iter().map(|(p, _)| *p).collect::<Vec<_>>();
+            let mut values = Vec::new();
+            let ordered = self.break_ties(group, &self.tiebreakers, &mut values);
+            for (pid, decided_by, shared) in ordered {
+                let position = standings.len() + 1;
+                let rank = match standings.last() {
+                    Some(prev) if shared & prev.shared & (prev.score == score) & decided_by.is_none() => prev.rank,
+                    _ => position,
+                };
+                let tiebreaks = values.iter()
+                    .filter(|(p, _, _)| *p == pid)
+                    .map(|(_, t, v)| (*t, *v))
+                    .collect();
+                standings.push(Standing {pid, rank, shared, score, decided_by, tiebreaks});
+            }
+            start = end;
+        }
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Game, Rpssl};
+    use super::*;
+
+    #[test]
+    fn tiebreakers() {
+        let mut game = Game::with_seed(1);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_player("Charlie");
+        let _ = game.add_player("David");
+
+        // Alice and Charlie both have 8 points, Charlie won their match
+        game.add_result((1, 2, 1), Rpssl::Rock, Rpssl::Scissors);
+        game.add_result((1, 3, 1), Rpssl::Rock, Rpssl::Paper);
+        let standings = game.get_standings();
+        assert_eq!(standings.iter().map(|s| s.pid).collect::<Vec<_>>(), vec![3, 1, 2, 4]);
+        assert_eq!(standings[1].decided_by, Some(Tiebreaker::HeadToHead));
+        assert_eq!(standings[1].rank_str(), "2.");
+        assert_eq!(standings[0].decided_by, None);
+
+        // Without any tiebreakers the rank is shared
+        game.set_tiebreakers(vec![]);
+        let standings = game.get_standings();
+        assert_eq!(standings[0].rank_str(), "=1.");
+        assert_eq!(standings[1].rank_str(), "=1.");
+        assert_eq!(standings[2].rank_str(), "3.");
+
+        // Alice and Charlie have one win each, Alice has played more
+        game.set_tiebreakers(vec![Tiebreaker::Wins, Tiebreaker::FewestPlayed]);
+        let standings = game.get_standings();
+        assert_eq!(standings.iter().map(|s| s.pid).collect::<Vec<_>>(), vec![3, 1, 2, 4]);
+        assert_eq!(standings[1].decided_by, Some(Tiebreaker::FewestPlayed));
+        assert_eq!(standings[1].tiebreaks, vec![(Tiebreaker::Wins, 1.0), (Tiebreaker::FewestPlayed, -2.0)]);
+    }
+
+    #[test]
+    fn standings_history() {
+        let mut game = Game::with_seed(1);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_player("Charlie");
+        game.add_result((1, 2, 1), Rpssl::Rock, Rpssl::Scissors);
+        game.add_result((1, 3, 1), Rpssl::Rock, Rpssl::Paper);
+
+        let history = game.get_standings_history();
+        assert_eq!(history.steps(), 3);
+        assert_eq!(history.matches, vec![(1, 2, 1), (1, 3, 1)]);
+        assert_eq!(history.scores[&1], vec![0, 7, 8]);
+        assert_eq!(history.scores[&3], vec![0, 0, 8]);
+        assert_eq!(history.ranks[&1], vec![1, 1, 2]);
+        assert_eq!(history.ranks[&2], vec![1, 2, 3]);
+        assert_eq!(history.ranks[&3], vec![1, 3, 1]);
+        assert_eq!(history.surprise(1, 2, 0), None);
+        assert_eq!(history.surprise(3, 1, 1), Some(Surprise::LeaderBeaten));
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::BTreeMap;
+use strum::IntoEnumIterator;
+use crate::{Game, Match, Playable, Rpssl, RpsResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandStats {
+    pub hand: Rpssl,
+    pub thrown: u16,
+    pub wins: u16,
+    pub draws: u16,
+    pub losses: u16,
+}
+
+impl HandStats {
+    fn new(hand: Rpssl) -> Self {
+        HandStats {hand, thrown: 0, wins: 0, draws: 0, losses: 0}
+    }
+
+    // Rates are in percent of the times this hand was thrown
+    pub fn win_rate(&self) -> f64 {
+        rate(self.wins, self.thrown)
+    }
+
+    pub fn draw_rate(&self) -> f64 {
+        rate(self.draws, self.thrown)
+    }
+
+    pub fn loss_rate(&self) -> f64 {
+        rate(self.losses, self.thrown)
+    }
+}
+
+fn rate(n: u16, total: u16) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        100.0 * n as f64 / total as f64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerStats {
+    pub pid: u16,
+    pub played: u16,
+    // One entry per playable hand of the current game mode
+    pub hands: Vec<HandStats>,
+    pub result_points: u16,
+    pub hand_points: u16,
+    // Result and length of the ongoing streak
+    pub current_streak: Option<(RpsResult, u16)>,
+    pub longest_win_streak: u16,
+    pub longest_loss_streak: u16,
+    // Most common hand thrown in the match following a win or a loss
+    pub after_win: Option<Rpssl>,
+    pub after_loss: Option<Rpssl>,
+}
+
+impl PlayerStats {
+    pub fn get_hand(&self, hand: Rpssl) -> Option<&HandStats> {
+        self.hands.iter().find(|h| h.hand == hand)
+    }
+}
+
+fn most_common(counts: &BTreeMap<Rpssl, u16>) -> Option<Rpssl> {
+    // Ties go to the hand that comes first in the enum
+    let mut best: Option<(Rpssl, u16)> = None;
+    for hand in Rpssl::iter() {
+        if let Some(&n) = counts.get(&hand) {
+            if best.is_none_or(|(_, b)| n > b) {
+                best = Some((hand, n));
+            }
+        }
+    }
+    best.map(|(h, _)| h)
+}
+
+// Record of one player against one opponent
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeadToHead {
+    pub scheduled: u16,
+    pub played: u16,
+    pub wins: u16,
+    pub draws: u16,
+    pub losses: u16,
+    pub points: u16,
+    pub points_against: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crosstable {
+    // Row and column order, by player id
+    pub players: Vec<u16>,
+    // keys are (player, opponent), seen from the row player
+    pub cells: BTreeMap<(u16, u16), HeadToHead>,
+}
+
+impl Crosstable {
+    pub fn get(&self, pid: u16, opponent: u16) -> Option<&HeadToHead> {
+        self.cells.get(&(pid, opponent))
+    }
+}
+
+impl Game {
+    pub fn get_head_to_head(&self, pid: u16, opponent: u16) -> HeadToHead {
+        let game_mode = self.get_mode();
+        let mut h2h = HeadToHead::default();
+        for m in self.match_list.values() {
+            if !(((m.player1 == pid) & (m.player2 == opponent)) | ((m.player1 == opponent) & (m.player2 == pid))) {
+                continue;
+            }
+            h2h.scheduled += 1;
+            let Some(result) = m.get_result_for_player(pid) else {
+                continue;
+            };
+            h2h.played += 1;
+            match result {
+                RpsResult::Win => h2h.wins += 1,
+                RpsResult::Draw => h2h.draws += 1,
+                RpsResult::Lose => h2h.losses += 1,
+            }
+            h2h.points += m.get_score_for_player(pid, game_mode);
+            h2h.points_against += m.get_score_for_player(opponent, game_mode);
+        }
+        h2h
+    }
+
+    pub fn get_crosstable(&self) -> Crosstable {
+        let players = self.player_list.keys().copied().collect::<Vec<_>>();
+        let mut cells = BTreeMap::new();
+        for &p1 in &players {
+            for &p2 in &players {
+                if p1 != p2 {
+                    cells.insert((p1, p2), self.get_head_to_head(p1, p2));
+                }
+            }
+        }
+        Crosstable {players, cells}
+    }
+
+    pub fn get_player_stats(&self, pid: u16) -> Option<PlayerStats> {
+        self.player_list.get(&pid)?;
+        let game_mode = self.get_mode();
+        let mut hands = game_mode.hands().into_iter().map(HandStats::new).collect::<Vec<_>>();
+        let mut result_points = 0;
+        let mut hand_points = 0;
+        let mut current_streak: Option<(RpsResult, u16)> = None;
+        let mut longest_win_streak = 0;
+        let mut longest_loss_streak = 0;
+        let mut after_win = BTreeMap::new();
+        let mut after_loss = BTreeMap::new();
+        let mut previous: Option<RpsResult> = None;
+        let mut played = 0;
+
+        let matches = self.get_played_games().into_iter()
+            .map(|(m, _p)| m)
+            .filter(|m| (m.player1 == pid) | (m.player2 == pid))
+            .collect::<Vec<&Match>>();
+        for m in matches {
+            let (Some(result), Some(hand)) = (m.get_result_for_player(pid), m.get_play_for_player(pid)) else {
+                continue;
+            };
+            played += 1;
+            if let Some(h) = hands.iter_mut().find(|h| h.hand == hand) {
+                h.thrown += 1;
+                match result {
+                    RpsResult::Win => h.wins += 1,
+                    RpsResult::Draw => h.draws += 1,
+                    RpsResult::Lose => h.losses += 1,
+                }
+            }
+            result_points += result.get_score(game_mode);
+            hand_points += hand.get_score();
+
+            match previous {
+                Some(RpsResult::Win) => *after_win.entry(hand).or_insert(0) += 1,
+                Some(RpsResult::Lose) => *after_loss.entry(hand).or_insert(0) += 1,
+                _ => (),
+            }
+
+            current_streak = match current_streak {
+                Some((r, n)) if r == result => Some((r, n + 1)),
+                _ => Some((result.clone(), 1)),
+            };
+            match current_streak {
+                Some((RpsResult::Win, n)) => longest_win_streak = longest_win_streak.max(n),
+                Some((RpsResult::Lose, n)) => longest_loss_streak = longest_loss_streak.max(n),
+                _ => (),
+            }
+            previous = Some(result);
+        }
+
+        Some(PlayerStats {
+            pid,
+            played,
+            hands,
+            result_points,
+            hand_points,
+            current_streak,
+            longest_win_streak,
+            longest_loss_streak,
+            after_win: most_common(&after_win),
+            after_loss: most_common(&after_loss),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameMode;
+
+    #[test]
+    fn player_stats() {
+        let mut game = Game::with_seed(1);
+        let _ = game.set_mode(GameMode::RPSSL);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_player("Charlie");
+        game.set_rounds(2);
+
+        // Alice wins with Rock, then wins with Paper, then loses with Paper
+        game.add_result((1, 2, 1), Rpssl::Rock, Rpssl::Scissors);
+        game.add_result((1, 3, 1), Rpssl::Paper, Rpssl::Rock);
+        game.add_result((2, 1, 2), Rpssl::Scissors, Rpssl::Paper);
+
+        let stats = game.get_player_stats(1).unwrap();
+        assert_eq!(stats.played, 3);
+        assert_eq!(stats.hands.len(), 5);
+        let paper = stats.get_hand(Rpssl::Paper).unwrap();
+        assert_eq!(paper.thrown, 2);
+        assert_eq!(paper.wins, 1);
+        assert_eq!(paper.losses, 1);
+        assert_eq!(paper.win_rate(), 50.0);
+        assert_eq!(stats.get_hand(Rpssl::Rock).unwrap().win_rate(), 100.0);
+        assert_eq!(stats.result_points, 20);
+        assert_eq!(stats.hand_points, 5);
+        assert_eq!(stats.current_streak, Some((RpsResult::Lose, 1)));
+        assert_eq!(stats.longest_win_streak, 2);
+        assert_eq!(stats.longest_loss_streak, 1);
+        assert_eq!(stats.after_win, Some(Rpssl::Paper));
+        assert_eq!(stats.after_loss, None);
+
+        // Bob is player 2 in the first game and player 1 in the last one
+        let stats = game.get_player_stats(2).unwrap();
+        assert_eq!(stats.played, 2);
+        assert_eq!(stats.get_hand(Rpssl::Scissors).unwrap().thrown, 2);
+        assert_eq!(stats.longest_loss_streak, 1);
+        assert_eq!(stats.current_streak, Some((RpsResult::Win, 1)));
+        assert_eq!(stats.after_loss, Some(Rpssl::Scissors));
+
+        assert!(game.get_player_stats(9).is_none());
+    }
+
+    #[test]
+    fn crosstable() {
+        let mut game = Game::with_seed(1);
+        let _ = game.add_player("Alice");
+        let _ = game.add_player("Bob");
+        let _ = game.add_player("Charlie");
+        game.set_rounds(2);
+        game.add_result((1, 2, 1), Rpssl::Rock, Rpssl::Scissors);
+        game.add_result((2, 1, 2), Rpssl::Rock, Rpssl::Rock);
+
+        let table = game.get_crosstable();
+        assert_eq!(table.players, vec![1, 2, 3]);
+        assert_eq!(table.cells.len(), 6);
+        assert!(table.get(1, 1).is_none());
+
+        let alice_bob = table.get(1, 2).unwrap();
+        assert_eq!(alice_bob.scheduled, 2);
+        assert_eq!(alice_bob.played, 2);
+        assert_eq!((alice_bob.wins, alice_bob.draws, alice_bob.losses), (1, 1, 0));
+        assert_eq!(alice_bob.points, 6 + 1 + 3 + 1);
+        assert_eq!(alice_bob.points_against, 3 + 3 + 1);
+
+        let bob_alice = table.get(2, 1).unwrap();
+        assert_eq!((bob_alice.wins, bob_alice.draws, bob_alice.losses), (0, 1, 1));
+        assert_eq!(bob_alice.points, alice_bob.points_against);
+
+        let alice_charlie = table.get(1, 3).unwrap();
+        assert_eq!(alice_charlie.scheduled, 2);
+        assert_eq!(alice_charlie.played, 0);
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::BTreeMap;
+use rand::{Rng, RngCore};
+use serde::{Serialize, Deserialize};
+use strum_macros::EnumIter;
+use crate::{Game, GameMode, Playable, Rpssl};
+
+// Everything a strategy may look at when choosing a throw
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThrowContext {
+    pub game_mode: GameMode,
+    // Hands allowed in the current game mode
+    pub hands: Vec<Rpssl>,
+    // Throws in play order over the whole tournament
+    pub own_history: Vec<Rpssl>,
+    pub opponent_history: Vec<Rpssl>,
+}
+
+pub trait Strategy {
+    fn name(&self) -> &str;
+    fn throw(&self, context: &ThrowContext, rng: &mut dyn RngCore) -> Rpssl;
+}
+
+// Built-in strategies. Bot players store one of these so that games
+// with bots can still be saved and replayed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter, Serialize, Deserialize)]
+pub enum StrategyKind {
+    Random,
+    Frequency,
+    Markov,
+    BeatLast,
+    BonusWeighted,
+}
+
+impl StrategyKind {
+    pub fn str(&self) -> &str {
+        self.strategy().name()
+    }
+
+    pub fn strategy(&self) -> &'static dyn Strategy {
+        match self {
+            Self::Random => &UniformRandom,
+            Self::Frequency => &FrequencyCounter,
+            Self::Markov => &MarkovChain,
+            Self::BeatLast => &BeatLast,
+            Self::BonusWeighted => &BonusWeighted,
+        }
+    }
+}
+
+pub struct UniformRandom;
+pub struct FrequencyCounter;
+pub struct MarkovChain;
+pub struct BeatLast;
+pub struct BonusWeighted;
+
+fn random_hand(context: &ThrowContext, rng: &mut dyn RngCore) -> Rpssl {
+    context.hands[rng.gen_range(0..context.hands.len())]
+}
+
+fn counts(history: &[Rpssl]) -> BTreeMap<Rpssl, f64> {
+    let mut counts = BTreeMap::new();
+    for hand in history {
+        *counts.entry(*hand).or_insert(0.0) += 1.0;
+    }
+    counts
+}
+
+// Hand with the best expected score against the predicted opponent hands.
+// Equally good hands are chosen at random
+fn best_response(context: &ThrowContext, prediction: &BTreeMap<Rpssl, f64>, hand_bonus: bool, rng: &mut dyn RngCore) -> Rpssl {
+    if prediction.is_empty() {
+        return random_hand(context, rng);
+    }
+    let expected = context.hands.iter().map(|hand| {
+        let value = prediction.iter().map(|(other, weight)| {
+            let mut score = hand.result(other).get_score(context.game_mode) as f64;
+            if hand_bonus {
+                score += hand.get_score() as f64;
+            }
+            weight * score
+        }).sum::<f64>();
+        (*hand, value)
+    }).collect::<Vec<_>>();
+    let best = expected.iter().map(|(_, v)| *v).fold(f64::MIN, f64::max);
+    let candidates = expected.iter()
+        .filter(|(_, v)| best - v < 1e-9)
+        .map(|(h, _)| *h)
+        .collect::<Vec<_>>();
+    candidates[rng.gen_range(0..candidates.len())]
+}
+
+impl Strategy for UniformRandom {
+    fn name(&self) -> &str {
+        "Random"
+    }
+
+    fn throw(&self, context: &ThrowContext, rng: &mut dyn RngCore) -> Rpssl {
+        random_hand(context, rng)
+    }
+}
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> &str {
+        "Frequency"
+    }
+
+    fn throw(&self, context: &ThrowContext, rng: &mut dyn RngCore) -> Rpssl {
+        best_response(context, &counts(&context.opponent_history), false, rng)
+    }
+}
+
+impl Strategy for MarkovChain {
+    fn name(&self) -> &str {
+        "Markov"
+    }
+
+    // Predict the opponent's next hand from what they have thrown
+    // after their latest hand before
+    fn throw(&self, context: &ThrowContext, rng: &mut dyn RngCore) -> Rpssl {
+        let history = &context.opponent_history;
+        let Some(last) = history.last() else {
+            return random_hand(context, rng);
+        };
+        let mut transitions = BTreeMap::new();
+        for pair in history.windows(2) {
+            if &pair[0] == last {
+                *transitions.entry(pair[1]).or_insert(0.0) += 1.0;
+            }
+        }
+        if transitions.is_empty() {
+            transitions = counts(history);
+        }
+        best_response(context, &transitions, false, rng)
+    }
+}
+
+impl Strategy for BeatLast {
+    fn name(&self) -> &str {
+        "Beat last"
+    }
+
+    fn throw(&self, context: &ThrowContext, rng: &mut dyn RngCore) -> Rpssl {
+        let prediction = context.opponent_history.last()
+            .map(|h| BTreeMap::from([(*h, 1.0)]))
+            .unwrap_or_default();
+        best_response(context, &prediction, false, rng)
+    }
+}
+
+impl Strategy for BonusWeighted {
+    fn name(&self) -> &str {
+        "Bonus weighted"
+    }
+
+    // Expected score including the hand points, against the opponent's
+    // observed hands with every hand counted once as a prior
+    fn throw(&self, context: &ThrowContext, rng: &mut dyn RngCore) -> Rpssl {
+        let mut prediction = counts(&context.opponent_history);
+        for hand in &context.hands {
+            *prediction.entry(*hand).or_insert(0.0) += 1.0;
+        }
+        best_response(context, &prediction, true, rng)
+    }
+}
+
+impl Game {
+    fn get_history(&self, pid: u16) -> Vec<Rpssl> {
+        self.get_played_games().iter()
+            .filter_map(|(m, _p)| m.get_play_for_player(pid))
+            .collect()
+    }
+
+    pub fn get_throw_context(&self, pid: u16, opponent: u16) -> ThrowContext {
+        ThrowContext {
+            game_mode: self.get_mode(),
+            hands: self.get_mode().hands(),
+            own_history: self.get_history(pid),
+            opponent_history: self.get_history(opponent),
+        }
+    }
+
+    // Throw for player pid against opponent, drawn from the game's own RNG
+    pub fn throw_for(&mut self, strategy: &dyn Strategy, pid: u16, opponent: u16) -> Rpssl {
+        let context = self.get_throw_context(pid, opponent);
+        strategy.throw(&context, &mut self.rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use strum::IntoEnumIterator;
+
+    fn context(opponent_history: Vec<Rpssl>) -> ThrowContext {
+        ThrowContext {
+            game_mode: GameMode::RPSSL,
+            hands: GameMode::RPSSL.hands(),
+            own_history: vec![],
+            opponent_history,
+        }
+    }
+
+    #[test]
+    fn strategies() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let beaten_by = |h: Rpssl| GameMode::RPSSL.hands().into_iter()
+            .filter(|o| o.result(&h) == crate::RpsResult::Win)
+            .collect::<Vec<_>>();
+
+        let throw = BeatLast.throw(&context(vec![Rpssl::Paper, Rpssl::Rock]), &mut rng);
+        assert!(beaten_by(Rpssl::Rock).contains(&throw));
+
+        let throw = FrequencyCounter.throw(&context(vec![Rpssl::Lizard, Rpssl::Lizard, Rpssl::Rock]), &mut rng);
+        assert!(beaten_by(Rpssl::Lizard).contains(&throw));
+
+        // Rock is always followed by Scissors
+        let history = vec![Rpssl::Rock, Rpssl::Scissors, Rpssl::Paper, Rpssl::Rock, Rpssl::Scissors, Rpssl::Rock];
+        let throw = MarkovChain.throw(&context(history), &mut rng);
+        assert!(beaten_by(Rpssl::Scissors).contains(&throw));
+
+        // Against a uniform opponent the Vampire is worth the most
+        let throw = BonusWeighted.throw(&context(vec![]), &mut rng);
+        assert_eq!(throw, Rpssl::Vampire);
+
+        let rps = ThrowContext {game_mode: GameMode::RPS, hands: GameMode::RPS.hands(), ..context(vec![])};
+        for kind in StrategyKind::iter() {
+            for _ in 0..20 {
+                assert!(rps.hands.contains(&kind.strategy().throw(&rps, &mut rng)));
+            }
+        }
+    }
+
+    #[test]
+    fn bot_game() {
+        let mut game = Game::with_seed(3);
+        let _ = game.set_mode(GameMode::RPSSL);
+        let _ = game.add_player("Alice");
+        let _ = game.add_bot("Markov", StrategyKind::Markov);
+        let _ = game.add_bot("Random", StrategyKind::Random);
+        assert!(game.get_player(2).unwrap().is_bot());
+
+        game.add_result_with_bots((1, 2, 1), Rpssl::Rock, Rpssl::None);
+        game.add_result_with_bots((2, 3, 1), Rpssl::None, Rpssl::None);
+        assert_eq!(game.get_played_n(), 2);
+        let m = game.match_list.get(&(1, 2, 1)).unwrap();
+        assert_eq!(m.play1, Rpssl::Rock);
+        assert!(m.play2.is_some());
+
+        let replayed = Game::from_saved(&game.save()).unwrap();
+        assert_eq!(replayed.match_list, game.match_list);
+        assert_eq!(replayed.get_quote(), game.get_quote());
+    }
+}